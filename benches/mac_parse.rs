@@ -0,0 +1,52 @@
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use yrnu::core::MacAddress;
+
+/// Runs `f` the giving amount of times and returns the average duration of a run
+fn bench<F: FnMut()>(runs: u32, mut f: F) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs {
+        f();
+    }
+    start.elapsed() / runs
+}
+
+fn main() {
+    // A CAM table sized list of addresses from a handful of vendors
+    let ouis = ["00:1A:2B", "54:EE:75", "AC:12:00", "F4:CE:46", "00:50:56"];
+    let addresses = (0..50_000u32)
+        .map(|i| {
+            let host = i.to_be_bytes();
+            format!(
+                "{}:{:02X}:{:02X}:{:02X}",
+                ouis[i as usize % ouis.len()],
+                host[1],
+                host[2],
+                host[3]
+            )
+        })
+        .collect::<Vec<String>>();
+
+    let parse = bench(10, || {
+        black_box(MacAddress::parse_all(black_box(&addresses)));
+    });
+    println!("parse_all {} addresses: {:?}", addresses.len(), parse);
+
+    let macs = MacAddress::parse_all(&addresses[..100])
+        .into_iter()
+        .flatten()
+        .collect::<Vec<MacAddress>>();
+    let first_lookup = bench(1, || {
+        black_box(macs[0].vendor());
+    });
+    println!(
+        "first vendor lookup (parses the built-in database): {:?}",
+        first_lookup
+    );
+    let lookup = bench(10, || {
+        for mac in &macs {
+            black_box(mac.vendor());
+        }
+    });
+    println!("vendor lookup of {} addresses: {:?}", macs.len(), lookup);
+}
@@ -1,6 +1,6 @@
 use crate::core::IpAddress;
+use crate::error::{Error, ErrorKind::*};
 use ssh2::Session;
-use std::error::Error;
 use std::io::prelude::*;
 use std::path::PathBuf;
 
@@ -15,20 +15,26 @@ pub async fn connect(
     host: IpAddress,
     port: Option<u16>,
     auth: SSHAuthType,
-) -> Result<Session, Box<dyn Error>> {
-    let mut sess = Session::new()?;
+) -> Result<Session, Error> {
     let addr = host.address();
     let port = port.unwrap_or(22);
-    let sock = tokio::net::TcpStream::connect(format!("{addr}:{port}")).await;
-    if let Err(e) = sock {
-        return Err(Box::new(e));
-    }
-    sess.set_tcp_stream(sock.unwrap());
-    sess.handshake()?;
+    let target = format!("{addr}:{port}");
+    let failed = |e: ssh2::Error| Error::new(ConnectionFailed, &target).with_source(e);
+    let mut sess = Session::new().map_err(failed)?;
+    let sock = tokio::net::TcpStream::connect(&target)
+        .await
+        .map_err(|e| Error::new(ConnectionFailed, &target).with_source(e))?;
+    sess.set_tcp_stream(sock);
+    sess.handshake().map_err(failed)?;
 
+    let auth_failed = |user: &str| {
+        let input = format!("{}@{target}", user.trim());
+        move |e: ssh2::Error| Error::new(AuthenticationFailed, input).with_source(e)
+    };
     match auth {
         SSHAuthType::Arguments(user, passwd) => {
-            sess.userauth_password(user.trim(), passwd.trim())?;
+            sess.userauth_password(user.trim(), passwd.trim())
+                .map_err(auth_failed(&user))?;
         }
         SSHAuthType::UserInput => {
             let mut user = String::new();
@@ -42,7 +48,8 @@ pub async fn connect(
                 eprintln!("Couldn't read user password: {e}");
                 String::new()
             });
-            sess.userauth_password(user.trim(), passwd.trim())?;
+            sess.userauth_password(user.trim(), passwd.trim())
+                .map_err(auth_failed(&user))?;
         }
         SSHAuthType::KeyPair(user, public, private, passphrase) => {
             let public = if let Some(public) = public {
@@ -50,34 +57,33 @@ pub async fn connect(
             } else {
                 None
             };
-            sess.userauth_pubkey_file(&user, public.as_deref(), &private, passphrase.as_deref())?;
+            sess.userauth_pubkey_file(&user, public.as_deref(), &private, passphrase.as_deref())
+                .map_err(auth_failed(&user))?;
         }
         SSHAuthType::Agent(user) => {
-            sess.userauth_agent(&user)?;
+            sess.userauth_agent(&user).map_err(auth_failed(&user))?;
         }
     };
     Ok(sess)
 }
 
-pub fn run(
-    sess: &Session,
-    config: String,
-) -> Result<(Option<String>, Option<String>, i32), ssh2::Error> {
-    let mut chan = sess.channel_session()?;
+pub fn run(sess: &Session, config: String) -> Result<(Option<String>, Option<String>, i32), Error> {
+    let failed = |input: &str| {
+        let input = input.to_string();
+        move |e: ssh2::Error| Error::new(CommandFailed, input).with_source(e)
+    };
+    let mut chan = sess.channel_session().map_err(failed(""))?;
     let mut stdout = String::new();
     let mut stderr = String::new();
     for line in config.lines() {
-        match chan.exec(line) {
-            Ok(_) => {}
-            Err(e) => return Err(e),
-        };
+        chan.exec(line).map_err(failed(line))?;
         _ = chan.read_to_string(&mut stdout);
         _ = chan.stderr().read_to_string(&mut stderr);
     }
-    chan.wait_close()?;
+    chan.wait_close().map_err(failed(""))?;
     Ok((
         Some(stdout.trim().to_string()),
         Some(stderr.trim().to_string()),
-        chan.exit_status()?,
+        chan.exit_status().map_err(failed(""))?,
     ))
 }
@@ -1,16 +1,23 @@
 //! # address.rs
 //! The `address` module provides general purpose tools for handling and managing Ip and Mac
 //! addresses as well as defining networks and getting local network interfaces info.
-use crate::error::coreerr::*;
+use crate::error::{Error, ErrorKind::*};
 use core::fmt;
 use mlua::FromLua;
 use pnet::datalink::NetworkInterface;
 use pnet::{datalink::interfaces, ipnetwork::IpNetwork};
+use regex::RegexBuilder;
+use sha2::{Digest, Sha256};
 use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
     fmt::{Display, Formatter},
+    hash::{Hash, Hasher},
     net::{IpAddr, Ipv4Addr, Ipv6Addr, ToSocketAddrs},
+    ops::{Add, Sub},
     path::PathBuf,
     str::FromStr,
+    sync::{OnceLock, RwLock},
 };
 
 /// # MacAddress
@@ -18,11 +25,38 @@ use std::{
 #[derive(Debug, Clone, PartialEq, FromLua)]
 pub struct MacAddress {
     bytes: [u8; 6],
+}
+/// # MacNotation
+/// `MacNotation` - text notations of a MAC address enum.
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub enum MacNotation {
+    Colon,
+    Dash,
+    Bare,
+    Cisco,
+    Eui64,
+}
+/// # Oui
+/// `Oui` - block of MAC addresses the IEEE assigned to a vendor, a MA-L (/24), MA-M (/28) or
+/// MA-S (/36) block
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct Oui {
+    prefix: u64,
+    length: u8,
     vendor: String,
 }
+/// # OuiDatabase
+/// `OuiDatabase` - vendor database of the IEEE assigned MAC address blocks
+#[derive(Debug, Clone, Default)]
+pub struct OuiDatabase {
+    blocks: BTreeMap<u8, HashMap<u64, String>>,
+}
+/// The vendor database shipped with yrnu, in the Wireshark `manuf` format (the MIT licensed
+/// rsmanuf 2024.12.1 data, see `data/README.md`)
+const BUILTIN_OUI_DATABASE: &str = include_str!("../data/manuf");
 /// # IpVersion
 /// `IpVersion` - Internet Protocol (IP) versions enum.
-#[derive(Debug, Clone, PartialEq, FromLua)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, FromLua)]
 pub enum IpVersion {
     V4,
     V6,
@@ -43,11 +77,36 @@ pub enum IpKind {
     Netid,
     Multicast,
     Unspecified,
+    Shared,
+    Documentation,
+    Benchmarking,
+    Reserved,
+    SixToFour,
+    Teredo,
+    Ipv4Mapped,
+    Orchid,
+    Special,
+}
+
+/// # SpecialPurpose
+/// `SpecialPurpose` - entry of the IANA special-purpose address registries (RFC 6890)
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct SpecialPurpose {
+    network: Network,
+    name: String,
+    rfc: String,
+    kind: IpKind,
+    source: bool,
+    destination: bool,
+    forwardable: bool,
+    global: bool,
+    reserved_by_protocol: bool,
+    deprecated: bool,
 }
 
 /// # IpAddress
 /// `IpAddress` - Internet Protocol (IP) address (V4/V6) struct
-#[derive(Debug, Clone, PartialEq, FromLua)]
+#[derive(Debug, Clone, FromLua)]
 pub struct IpAddress {
     address: Vec<u8>,
     version: IpVersion,
@@ -59,7 +118,17 @@ pub struct IpAddress {
 #[derive(Debug, Clone, PartialEq, FromLua)]
 pub struct Mask {
     prefix: u8,
-    num_of_hosts: u32,
+    num_of_hosts: u128,
+    version: IpVersion,
+}
+
+/// # WildcardMask
+/// `WildcardMask` - ACL wildcard mask, the set bits are ignored when matching an address and
+/// they do not have to be contiguous
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct WildcardMask {
+    bits: u128,
+    version: IpVersion,
 }
 
 /// # Network
@@ -71,19 +140,49 @@ pub struct Network {
     broadcast: IpAddress,
 }
 
+/// # Subnets
+/// `Subnets` - iterator over the equally sized subnets of a network
+#[derive(Debug, Clone)]
+pub struct Subnets {
+    next: Option<u128>,
+    last: u128,
+    prefix: u8,
+    version: IpVersion,
+}
+
+/// # IpRange
+/// `IpRange` - inclusive range of IP addresses of the same version
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct IpRange {
+    first: IpAddress,
+    last: IpAddress,
+}
+
+/// # Hosts
+/// `Hosts` - lazy iterator over the addresses of a network or a range
+#[derive(Debug, Clone)]
+pub struct Hosts {
+    next: Option<u128>,
+    last: u128,
+    version: IpVersion,
+}
+
+/// # IpSet
+/// `IpSet` - set of IP networks backed by a binary prefix trie, every prefix carries a value
+#[derive(Debug, Clone)]
+pub struct IpSet<T = ()> {
+    v4: Option<Box<TrieNode<T>>>,
+    v6: Option<Box<TrieNode<T>>>,
+}
+
+/// A node of the `IpSet` prefix trie, the depth of the node is the prefix length
+#[derive(Debug, Clone)]
+struct TrieNode<T> {
+    value: Option<T>,
+    children: [Option<Box<TrieNode<T>>>; 2],
+}
+
 impl MacAddress {
-    /// Returns the giving mac address vendor
-    fn get_vendor(address: &str) -> Result<String, InvalidMacAddress> {
-        let index = rsmanuf::Index::new();
-        if Self::is_valid(address) {
-            Ok(match index.search(address) {
-                Ok(manuf) => manuf,
-                Err(_) => String::from("Unknown"),
-            })
-        } else {
-            Err(InvalidMacAddress)
-        }
-    }
     /// Checks if a giving `mac address` is valid
     pub fn is_valid(address: &str) -> bool {
         Self::get_parts(address).is_ok()
@@ -92,35 +191,54 @@ impl MacAddress {
     pub fn as_bytes(&self) -> &[u8; 6] {
         &self.bytes
     }
-    /// Converts string mac address into byte array
-    fn get_parts(address: &str) -> Result<[u8; 6], InvalidMacAddress> {
-        let parts: Vec<Result<u8, _>> = address
-            .split(':')
-            .map(|i| u8::from_str_radix(i, 16))
-            .collect();
-        if parts.len() != 6 {
-            return Err(InvalidMacAddress);
-        }
-        let mut octets: [u8; 6] = [0u8; 6];
-        for (i, part) in parts.iter().enumerate() {
-            if let Ok(part) = part {
-                octets[i] = *part;
-            } else {
-                return Err(InvalidMacAddress);
+    /// Converts string mac address into byte array, the colon, dash, bare hex, Cisco dotted and
+    /// EUI-64 (`FF:FE` in the middle) notations are supported
+    fn get_parts(address: &str) -> Result<[u8; 6], Error> {
+        let parse_hex = |part: &str, max_len: usize| {
+            if part.is_empty()
+                || part.len() > max_len
+                || !part.chars().all(|c| c.is_ascii_hexdigit())
+            {
+                return None;
             }
-        }
-        Ok(octets)
+            u16::from_str_radix(part, 16).ok()
+        };
+        let parse = || {
+            let mut octets = vec![];
+            if address.contains('.') {
+                for part in address.split('.') {
+                    if part.len() != 4 {
+                        return None;
+                    }
+                    octets.extend_from_slice(&parse_hex(part, 4)?.to_be_bytes());
+                }
+            } else if let Some(separator) = [':', '-'].into_iter().find(|c| address.contains(*c)) {
+                for part in address.split(separator) {
+                    octets.push(parse_hex(part, 2)? as u8);
+                }
+            } else if address.len().is_multiple_of(2) {
+                for i in (0..address.len()).step_by(2) {
+                    octets.push(parse_hex(address.get(i..i + 2)?, 2)? as u8);
+                }
+            }
+            if octets.len() == 8 && octets[3] == 0xff && octets[4] == 0xfe {
+                octets.drain(3..5);
+            }
+            <[u8; 6]>::try_from(octets).ok()
+        };
+        parse().ok_or_else(|| Error::new(InvalidMacAddress, address))
     }
     /// Creates a new MacAddress instance
     pub fn new(bytes: [u8; 6]) -> MacAddress {
-        MacAddress {
-            bytes,
-            vendor: Self::get_vendor(&format!(
-                "{:02X}:{:02X}:{:02X}:{:02X}:{:02X}:{:02X}",
-                bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5]
-            ))
-            .unwrap(),
-        }
+        MacAddress { bytes }
+    }
+    /// Parses a list of mac addresses, the vendor index is not touched so even large tables
+    /// (a CAM table dump for example) are parsed quickly
+    pub fn parse_all<S: AsRef<str>>(addresses: &[S]) -> Vec<Result<MacAddress, Error>> {
+        addresses
+            .iter()
+            .map(|address| Self::get_parts(address.as_ref()).map(MacAddress::new))
+            .collect()
     }
     /// Returns the address as a string
     pub fn address(&self) -> String {
@@ -145,8 +263,81 @@ impl MacAddress {
             self.bytes[5],
         )
     }
-    pub fn vendor(&self) -> &String {
-        &self.vendor
+    /// Returns the address vendor, the lookup is done on demand against the shared vendor database
+    /// and `Unknown` is returned for an address that has no vendor
+    pub fn vendor(&self) -> String {
+        match OuiDatabase::shared().read().unwrap().lookup(self) {
+            Some(oui) => oui.vendor,
+            None => String::from("Unknown"),
+        }
+    }
+    /// Returns every vendor block whose vendor name matches the giving pattern in the shared
+    /// vendor database
+    pub fn ouis_for_vendor(pattern: &str) -> Vec<Oui> {
+        OuiDatabase::shared()
+            .read()
+            .unwrap()
+            .ouis_for_vendor(pattern)
+    }
+    /// Loads an IEEE `oui.csv` or a Wireshark `manuf` file into the shared vendor database, the
+    /// blocks of the file override the built-in ones
+    pub fn load_vendor_database(path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+        let database = OuiDatabase::load(path)?;
+        OuiDatabase::shared().write().unwrap().extend(database);
+        Ok(())
+    }
+    /// Returns the address in a giving notation
+    pub fn format(&self, notation: &MacNotation) -> String {
+        let hex = |bytes: &[u8]| {
+            bytes
+                .iter()
+                .map(|byte| format!("{:02X}", byte))
+                .collect::<Vec<String>>()
+        };
+        match notation {
+            MacNotation::Colon => self.address(),
+            MacNotation::Dash => hex(&self.bytes).join("-"),
+            MacNotation::Bare => hex(&self.bytes).concat(),
+            MacNotation::Cisco => self.cisco_format(),
+            MacNotation::Eui64 => {
+                let mut bytes = self.bytes.to_vec();
+                bytes.splice(3..3, [0xff, 0xfe]);
+                hex(&bytes).join(":")
+            }
+        }
+    }
+    /// Checks if the address is a group (multicast) address, the I/G bit is set
+    pub fn is_multicast(&self) -> bool {
+        self.bytes[0] & 0b0000_0001 != 0
+    }
+    /// Checks if the address is an individual (unicast) address, the I/G bit is not set
+    pub fn is_unicast(&self) -> bool {
+        !self.is_multicast()
+    }
+    /// Checks if the address is locally administered, the U/L bit is set
+    pub fn is_local(&self) -> bool {
+        self.bytes[0] & 0b0000_0010 != 0
+    }
+    /// Checks if the address is universally administered (assigned by a vendor), the U/L bit is
+    /// not set
+    pub fn is_universal(&self) -> bool {
+        !self.is_local()
+    }
+    /// Checks if the address is the broadcast address `FF:FF:FF:FF:FF:FF`
+    pub fn is_broadcast(&self) -> bool {
+        self.bytes == [0xff; 6]
+    }
+    /// Generates a random unicast address from the random source of the OS, with the giving
+    /// vendor OUI or a locally administered address when no OUI is giving
+    pub fn random(oui: Option<[u8; 3]>) -> Result<MacAddress, Error> {
+        let mut bytes = [0u8; 6];
+        getrandom::getrandom(&mut bytes)
+            .map_err(|e| Error::new(RandomFailed, "").with_source(e))?;
+        match oui {
+            Some(oui) => bytes[..3].copy_from_slice(&oui),
+            None => bytes[0] = (bytes[0] & 0b1111_1100) | 0b0000_0010,
+        }
+        Ok(MacAddress::new(bytes))
     }
 }
 
@@ -178,15 +369,237 @@ impl PartialOrd for MacAddress {
 }
 
 impl FromStr for MacAddress {
-    type Err = InvalidMacAddress;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        if MacAddress::is_valid(s) {
-            return Ok(MacAddress {
-                bytes: Self::get_parts(s).unwrap(),
-                vendor: Self::get_vendor(s).unwrap(),
-            });
+        Ok(MacAddress::new(Self::get_parts(s)?))
+    }
+}
+
+impl Display for MacNotation {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MacNotation::Colon => write!(f, "colon"),
+            MacNotation::Dash => write!(f, "dash"),
+            MacNotation::Bare => write!(f, "bare"),
+            MacNotation::Cisco => write!(f, "cisco"),
+            MacNotation::Eui64 => write!(f, "eui64"),
+        }
+    }
+}
+
+impl Oui {
+    /// Creates a new Oui instance, the bits of the prefix after the block length are ignored
+    pub fn new(prefix: &MacAddress, length: u8, vendor: &str) -> Result<Oui, Error> {
+        if !(1..=48).contains(&length) {
+            return Err(Error::new(InvalidOuiDatabase, format!("{prefix}/{length}")));
+        }
+        let mut bytes = [0u8; 8];
+        bytes[2..].copy_from_slice(prefix.as_bytes());
+        Ok(Oui {
+            prefix: u64::from_be_bytes(bytes) & Self::mask(length),
+            length,
+            vendor: vendor.to_string(),
+        })
+    }
+    /// Returns the first address of the block
+    pub fn prefix(&self) -> MacAddress {
+        MacAddress::new(self.prefix.to_be_bytes()[2..].try_into().unwrap())
+    }
+    /// Returns the length of the block prefix in bits
+    pub fn length(&self) -> u8 {
+        self.length
+    }
+    pub fn vendor(&self) -> &str {
+        &self.vendor
+    }
+    /// Checks if a giving mac address belongs to the block
+    pub fn contains(&self, address: &MacAddress) -> bool {
+        let mut bytes = [0u8; 8];
+        bytes[2..].copy_from_slice(address.as_bytes());
+        u64::from_be_bytes(bytes) & Self::mask(self.length) == self.prefix
+    }
+    /// Returns the 48 bits mask of a prefix length
+    fn mask(length: u8) -> u64 {
+        (0xffff_ffff_ffff >> (48 - length as u32)) << (48 - length as u32)
+    }
+    /// Parses a block prefix as written in the vendor databases, `00:1A:2B`, `001A2B`,
+    /// `70:B3:D5:10:00:00/28` or a MA-M/MA-S assignment like `70B3D51`
+    fn parse_prefix(prefix: &str) -> Option<(u64, u8)> {
+        let (address, length) = match prefix.split_once('/') {
+            Some((address, length)) => (address, Some(length.parse::<u8>().ok()?)),
+            None => (prefix, None),
+        };
+        let digits = address.replace([':', '-', '.'], "");
+        if digits.is_empty() || digits.len() > 12 || !digits.chars().all(|c| c.is_ascii_hexdigit())
+        {
+            return None;
+        }
+        let length = length.unwrap_or(digits.len() as u8 * 4);
+        if !(1..=48).contains(&length) {
+            return None;
+        }
+        let value = u64::from_str_radix(&digits, 16).ok()? << (48 - digits.len() * 4);
+        Some((value & Self::mask(length), length))
+    }
+}
+
+impl Display for Oui {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.length == 24 {
+            write!(f, "{}", &self.prefix().address()[..8])
+        } else {
+            write!(f, "{}/{}", self.prefix(), self.length)
+        }
+    }
+}
+
+impl OuiDatabase {
+    /// Creates a new empty OuiDatabase instance
+    pub fn new() -> OuiDatabase {
+        OuiDatabase::default()
+    }
+    /// Returns the vendor database shipped with yrnu
+    pub fn builtin() -> OuiDatabase {
+        Self::parse_manuf(BUILTIN_OUI_DATABASE).expect("the built-in vendor database is valid")
+    }
+    /// Returns the database used by the `MacAddress` vendor lookups, the built-in database is
+    /// parsed once on the first use
+    fn shared() -> &'static RwLock<OuiDatabase> {
+        static DATABASE: OnceLock<RwLock<OuiDatabase>> = OnceLock::new();
+        DATABASE.get_or_init(|| RwLock::new(OuiDatabase::builtin()))
+    }
+    /// Parses a database in the Wireshark `manuf` format, a tab separated prefix and vendor
+    /// names per line
+    pub fn parse_manuf(data: &str) -> Result<OuiDatabase, Error> {
+        let mut database = OuiDatabase::new();
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let fields = line
+                .split('\t')
+                .map(|field| field.trim())
+                .filter(|field| !field.is_empty())
+                .collect::<Vec<&str>>();
+            if fields.len() < 2 {
+                return Err(Error::new(InvalidOuiDatabase, line));
+            }
+            let (prefix, length) =
+                Oui::parse_prefix(fields[0]).ok_or_else(|| Error::new(InvalidOuiDatabase, line))?;
+            database
+                .blocks
+                .entry(length)
+                .or_default()
+                .insert(prefix, fields[fields.len() - 1].to_string());
+        }
+        Ok(database)
+    }
+    /// Parses a database in the IEEE registry csv format (`oui.csv`, `mam.csv`, `oui36.csv`)
+    pub fn parse_csv(data: &str) -> Result<OuiDatabase, Error> {
+        let mut reader = csv::ReaderBuilder::new().from_reader(data.as_bytes());
+        let headers = reader
+            .headers()
+            .map_err(|e| Error::new(InvalidOuiDatabase, "").with_source(e))?
+            .clone();
+        let column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.trim() == name)
+                .ok_or_else(|| Error::new(InvalidOuiDatabase, headers.as_slice()))
+        };
+        let (assignment, organization) = (column("Assignment")?, column("Organization Name")?);
+        let mut database = OuiDatabase::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| Error::new(InvalidOuiDatabase, "").with_source(e))?;
+            let invalid = || Error::new(InvalidOuiDatabase, record.as_slice());
+            let (prefix, length) = record
+                .get(assignment)
+                .and_then(Oui::parse_prefix)
+                .ok_or_else(invalid)?;
+            let vendor = record.get(organization).ok_or_else(invalid)?.trim();
+            database
+                .blocks
+                .entry(length)
+                .or_default()
+                .insert(prefix, vendor.to_string());
+        }
+        Ok(database)
+    }
+    /// Loads a database file, both the IEEE csv and the Wireshark `manuf` formats are supported
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<OuiDatabase, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(InvalidOuiDatabase, path.display()).with_source(e))?;
+        if data.trim_start().starts_with("Registry,") {
+            Self::parse_csv(&data)
+        } else {
+            Self::parse_manuf(&data)
+        }
+    }
+    /// Adds a vendor block to the database, returns the vendor it replaced if the block was
+    /// already in the database
+    pub fn insert(&mut self, oui: Oui) -> Option<String> {
+        self.blocks
+            .entry(oui.length)
+            .or_default()
+            .insert(oui.prefix, oui.vendor)
+    }
+    /// Adds the blocks of another database, the blocks of `other` override the existing ones
+    pub fn extend(&mut self, other: OuiDatabase) {
+        for (length, blocks) in other.blocks {
+            self.blocks.entry(length).or_default().extend(blocks);
         }
-        Err(InvalidMacAddress)
+    }
+    /// Returns the most specific block a giving mac address belongs to
+    pub fn lookup(&self, address: &MacAddress) -> Option<Oui> {
+        let mut bytes = [0u8; 8];
+        bytes[2..].copy_from_slice(address.as_bytes());
+        let value = u64::from_be_bytes(bytes);
+        self.blocks.iter().rev().find_map(|(length, blocks)| {
+            let prefix = value & Oui::mask(*length);
+            blocks.get(&prefix).map(|vendor| Oui {
+                prefix,
+                length: *length,
+                vendor: vendor.to_owned(),
+            })
+        })
+    }
+    /// Returns every block whose vendor name matches the giving pattern, the pattern is a case
+    /// insensitive regular expression and matched literally when it is not a valid one
+    pub fn ouis_for_vendor(&self, pattern: &str) -> Vec<Oui> {
+        let regex = RegexBuilder::new(pattern)
+            .case_insensitive(true)
+            .build()
+            .unwrap_or_else(|_| {
+                RegexBuilder::new(&regex::escape(pattern))
+                    .case_insensitive(true)
+                    .build()
+                    .unwrap()
+            });
+        let mut ouis = self
+            .blocks
+            .iter()
+            .flat_map(|(length, blocks)| {
+                blocks
+                    .iter()
+                    .filter(|(_, vendor)| regex.is_match(vendor))
+                    .map(|(prefix, vendor)| Oui {
+                        prefix: *prefix,
+                        length: *length,
+                        vendor: vendor.to_owned(),
+                    })
+            })
+            .collect::<Vec<Oui>>();
+        ouis.sort_by_key(|oui| (oui.prefix, oui.length));
+        ouis
+    }
+    /// Returns the number of blocks in the database
+    pub fn len(&self) -> usize {
+        self.blocks.values().map(|blocks| blocks.len()).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -205,6 +618,18 @@ impl IpVersion {
             Err(..) => false,
         }
     }
+    /// Returns the number of bits in an address of the version
+    pub fn bits(&self) -> u8 {
+        match self {
+            IpVersion::V4 => 32,
+            IpVersion::V6 => 128,
+        }
+    }
+    /// Returns the bit of an address integer value at the giving depth from the most significant
+    /// bit
+    fn bit(&self, key: u128, depth: u8) -> usize {
+        ((key >> (self.bits() - 1 - depth)) & 1) as usize
+    }
 }
 
 impl Display for IpVersion {
@@ -225,12 +650,9 @@ impl IpKind {
                 Err(..) => false,
             }
         } else if IpVersion::is_v6(address) {
-            if address.to_lowercase().starts_with("fd00")
-                || address.to_lowercase().starts_with("fc00")
-            {
-                true
-            } else {
-                false
+            match Ipv6Addr::from_str(address) {
+                Ok(addr) => addr.segments()[0] & 0xfe00 == 0xfc00,
+                Err(..) => false,
             }
         } else {
             false
@@ -254,34 +676,22 @@ impl IpKind {
     }
     /// Check if a giving Ipv4 address is a broadcast address
     pub fn is_broadcast(address: &str, mask: &Mask) -> bool {
-        if IpVersion::is_v4(address) {
-            let octats = IpAddress::octets_from_str(address).unwrap();
-            IpKind::is_netid(
-                format!(
-                    "{}.{}.{}.{}",
-                    octats[0],
-                    octats[1],
-                    octats[2],
-                    octats[3] as u32 - mask.num_of_hosts() - 1
-                )
-                .as_str(),
-                mask,
-            )
-        } else {
-            false
+        match IpAddress::from_str(address) {
+            Ok(addr) if *addr.version() == IpVersion::V4 && *mask.version() == IpVersion::V4 => {
+                let host_bits = Network::host_bits_mask(*mask.prefix(), mask.version());
+                addr.as_u128() & host_bits == host_bits
+            }
+            _ => false,
         }
     }
-    /// Checks if a giving Ipv4 address is a net id based on giving subnet mask
+    /// Checks if a giving Ip address is a net id based on giving subnet mask
     pub fn is_netid(address: &str, mask: &Mask) -> bool {
-        if !IpVersion::is_v4(address) {
-            return false;
-        }
-        let hosts = mask.num_of_hosts();
-        let octets = IpAddress::octets_from_str(address).unwrap();
-        if *hosts == u32::MAX {
-            return true;
+        match IpAddress::from_str(address) {
+            Ok(addr) if addr.version() == mask.version() => {
+                addr.as_u128() & Network::host_bits_mask(*mask.prefix(), mask.version()) == 0
+            }
+            _ => false,
         }
-        octets[3] as u32 % (hosts + 2) == 0
     }
     /// Check is a giving Ip address is a multicast address
     pub fn is_multicast(address: &str) -> bool {
@@ -301,14 +711,9 @@ impl IpKind {
     }
     /// Check if a giving Ip address is a linklocal address
     pub fn is_linklocal(address: &str) -> bool {
-        if IpVersion::is_v6(address) {
-            if address[0..4].to_lowercase() == "fe80" {
-                true
-            } else {
-                false
-            }
-        } else {
-            false
+        match Ipv6Addr::from_str(address) {
+            Ok(addr) => addr.segments()[0] & 0xffc0 == 0xfe80,
+            Err(..) => false,
         }
     }
     /// Check if a giving Ip address is an apipa address
@@ -338,83 +743,172 @@ impl IpKind {
             false
         }
     }
-    /// Check if a giving Ip address is a public address
+    /// IANA special-purpose address registries (RFC 6890 and the RFCs that updated it), every
+    /// entry holds the network, name, RFC, kind and the source, destination, forwardable,
+    /// globally reachable, reserved-by-protocol and deprecated flags
+    #[rustfmt::skip]
+    const SPECIAL_PURPOSE_REGISTRY: &[(&str, &str, &str, IpKind, [bool; 6])] = &[
+        ("0.0.0.0/8", "This network", "RFC791", IpKind::Reserved, [true, false, false, false, true, false]),
+        ("0.0.0.0/32", "This host on this network", "RFC1122", IpKind::Reserved, [true, false, false, false, true, false]),
+        ("10.0.0.0/8", "Private-Use", "RFC1918", IpKind::Private, [true, true, true, false, false, false]),
+        ("100.64.0.0/10", "Shared Address Space", "RFC6598", IpKind::Shared, [true, true, true, false, false, false]),
+        ("127.0.0.0/8", "Loopback", "RFC1122", IpKind::Loopback, [false, false, false, false, true, false]),
+        ("169.254.0.0/16", "Link Local", "RFC3927", IpKind::Apipa, [true, true, false, false, true, false]),
+        ("172.16.0.0/12", "Private-Use", "RFC1918", IpKind::Private, [true, true, true, false, false, false]),
+        ("192.0.0.0/24", "IETF Protocol Assignments", "RFC6890", IpKind::Special, [false, false, false, false, false, false]),
+        ("192.0.0.0/29", "IPv4 Service Continuity Prefix", "RFC7335", IpKind::Special, [true, true, true, false, false, false]),
+        ("192.0.0.8/32", "IPv4 dummy address", "RFC7600", IpKind::Special, [true, false, false, false, false, false]),
+        ("192.0.0.9/32", "Port Control Protocol Anycast", "RFC7723", IpKind::Special, [true, true, true, true, false, false]),
+        ("192.0.0.10/32", "Traversal Using Relays around NAT Anycast", "RFC8155", IpKind::Special, [true, true, true, true, false, false]),
+        ("192.0.0.170/32", "NAT64/DNS64 Discovery", "RFC8880", IpKind::Special, [false, false, false, false, true, false]),
+        ("192.0.0.171/32", "NAT64/DNS64 Discovery", "RFC8880", IpKind::Special, [false, false, false, false, true, false]),
+        ("192.0.2.0/24", "Documentation (TEST-NET-1)", "RFC5737", IpKind::Documentation, [false, false, false, false, false, false]),
+        ("192.31.196.0/24", "AS112-v4", "RFC7535", IpKind::Special, [true, true, true, true, false, false]),
+        ("192.52.193.0/24", "AMT", "RFC7450", IpKind::Special, [true, true, true, true, false, false]),
+        ("192.88.99.0/24", "Deprecated (6to4 Relay Anycast)", "RFC7526", IpKind::SixToFour, [false, false, false, false, false, true]),
+        ("192.88.99.2/32", "6a44-relay anycast address", "RFC6751", IpKind::Special, [true, true, true, false, false, false]),
+        ("192.168.0.0/16", "Private-Use", "RFC1918", IpKind::Private, [true, true, true, false, false, false]),
+        ("192.175.48.0/24", "Direct Delegation AS112 Service", "RFC7534", IpKind::Special, [true, true, true, true, false, false]),
+        ("198.18.0.0/15", "Benchmarking", "RFC2544", IpKind::Benchmarking, [true, true, true, false, false, false]),
+        ("198.51.100.0/24", "Documentation (TEST-NET-2)", "RFC5737", IpKind::Documentation, [false, false, false, false, false, false]),
+        ("203.0.113.0/24", "Documentation (TEST-NET-3)", "RFC5737", IpKind::Documentation, [false, false, false, false, false, false]),
+        ("240.0.0.0/4", "Reserved", "RFC1112", IpKind::Reserved, [false, false, false, false, true, false]),
+        ("255.255.255.255/32", "Limited Broadcast", "RFC8190", IpKind::Broadcast, [false, true, false, false, true, false]),
+        ("::1/128", "Loopback Address", "RFC4291", IpKind::Loopback, [false, false, false, false, true, false]),
+        ("::/128", "Unspecified Address", "RFC4291", IpKind::Unspecified, [true, false, false, false, true, false]),
+        ("::ffff:0:0/96", "IPv4-mapped Address", "RFC4291", IpKind::Ipv4Mapped, [false, false, false, false, true, false]),
+        ("64:ff9b::/96", "IPv4-IPv6 Translat.", "RFC6052", IpKind::Special, [true, true, true, true, false, false]),
+        ("64:ff9b:1::/48", "IPv4-IPv6 Translat.", "RFC8215", IpKind::Special, [true, true, true, false, false, false]),
+        ("100::/64", "Discard-Only Address Block", "RFC6666", IpKind::Special, [true, true, true, false, false, false]),
+        ("2001::/23", "IETF Protocol Assignments", "RFC2928", IpKind::Special, [false, false, false, false, false, false]),
+        ("2001::/32", "TEREDO", "RFC4380", IpKind::Teredo, [true, true, true, false, false, false]),
+        ("2001:1::1/128", "Port Control Protocol Anycast", "RFC7723", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:1::2/128", "Traversal Using Relays around NAT Anycast", "RFC8155", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:1::3/128", "DNS-SD Service Registration Protocol Anycast", "RFC9665", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:2::/48", "Benchmarking", "RFC5180", IpKind::Benchmarking, [true, true, true, false, false, false]),
+        ("2001:3::/32", "AMT", "RFC7450", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:4:112::/48", "AS112-v6", "RFC7535", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:10::/28", "Deprecated (previously ORCHID)", "RFC4843", IpKind::Orchid, [false, false, false, false, false, true]),
+        ("2001:20::/28", "ORCHIDv2", "RFC7343", IpKind::Orchid, [true, true, true, true, false, false]),
+        ("2001:30::/28", "Drone Remote ID Protocol Entity Tags (DETs) Prefix", "RFC9374", IpKind::Special, [true, true, true, true, false, false]),
+        ("2001:db8::/32", "Documentation", "RFC3849", IpKind::Documentation, [false, false, false, false, false, false]),
+        ("2002::/16", "6to4", "RFC3056", IpKind::SixToFour, [true, true, true, false, false, false]),
+        ("2620:4f:8000::/48", "Direct Delegation AS112 Service", "RFC7534", IpKind::Special, [true, true, true, true, false, false]),
+        ("3fff::/20", "Documentation", "RFC9637", IpKind::Documentation, [false, false, false, false, false, false]),
+        ("5f00::/16", "Segment Routing (SRv6) SIDs", "RFC9602", IpKind::Special, [true, true, true, false, false, false]),
+        ("fc00::/7", "Unique-Local", "RFC4193", IpKind::Uniqelocal, [true, true, true, false, false, false]),
+        ("fe80::/10", "Link-Local Unicast", "RFC4291", IpKind::Linklocal, [true, true, false, false, true, false]),
+    ];
+    /// Returns the registry networks as integer values, prefixes and versions
+    fn special_purpose_networks() -> &'static [(u128, u8, IpVersion)] {
+        static NETWORKS: OnceLock<Vec<(u128, u8, IpVersion)>> = OnceLock::new();
+        NETWORKS.get_or_init(|| {
+            Self::SPECIAL_PURPOSE_REGISTRY
+                .iter()
+                .map(|(network, ..)| {
+                    let (id, prefix) = network.split_once('/').unwrap();
+                    let prefix = prefix.parse::<u8>().unwrap();
+                    match IpAddr::from_str(id).unwrap() {
+                        IpAddr::V4(id) => (id.to_bits() as u128, prefix, IpVersion::V4),
+                        IpAddr::V6(id) => (id.to_bits(), prefix, IpVersion::V6),
+                    }
+                })
+                .collect()
+        })
+    }
+    /// Returns the index of the most specific registry entry that contains the giving address
+    fn special_purpose_index(address: &IpAddr) -> Option<usize> {
+        let (value, version) = match address {
+            IpAddr::V4(addr) => (addr.to_bits() as u128, IpVersion::V4),
+            IpAddr::V6(addr) => (addr.to_bits(), IpVersion::V6),
+        };
+        Self::special_purpose_networks()
+            .iter()
+            .enumerate()
+            .filter(|(_, (id, prefix, net_version))| {
+                *net_version == version
+                    && value & !Network::host_bits_mask(*prefix, &version) == *id
+            })
+            .max_by_key(|(_, (_, prefix, _))| *prefix)
+            .map(|(index, _)| index)
+    }
+    /// Returns the special-purpose registry entry of a giving address if there is one
+    pub fn special_purpose(address: &str) -> Option<SpecialPurpose> {
+        let index = Self::special_purpose_index(&IpAddr::from_str(address).ok()?)?;
+        let (id, prefix, version) = &Self::special_purpose_networks()[index];
+        let (_, name, rfc, kind, flags) = &Self::SPECIAL_PURPOSE_REGISTRY[index];
+        Some(SpecialPurpose {
+            network: Network::from_u128(*id, *prefix, version),
+            name: name.to_string(),
+            rfc: rfc.to_string(),
+            kind: kind.clone(),
+            source: flags[0],
+            destination: flags[1],
+            forwardable: flags[2],
+            global: flags[3],
+            reserved_by_protocol: flags[4],
+            deprecated: flags[5],
+        })
+    }
+    /// Check if a giving Ip address is a public (globally reachable) address
     pub fn is_public(address: &str) -> bool {
-        if IpKind::is_loopback(address)
-            || IpKind::is_private(address)
-            || IpKind::is_apipa(address)
-            || IpKind::is_multicast(address)
-            || IpKind::is_linklocal(address)
-            || IpKind::is_unspecified(address)
-        {
-            false
-        } else {
-            if IpVersion::is_v4(address) || IpVersion::is_v6(address) {
-                true
-            } else {
-                false
-            }
+        match IpAddr::from_str(address) {
+            Ok(addr) => match Self::special_purpose_index(&addr) {
+                Some(index) => Self::SPECIAL_PURPOSE_REGISTRY[index].4[3],
+                None => !addr.is_multicast(),
+            },
+            Err(..) => false,
+        }
+    }
+    /// Check if routers may forward packets with a giving Ip address
+    pub fn is_forwardable(address: &str) -> bool {
+        match IpAddr::from_str(address) {
+            Ok(addr) => match Self::special_purpose_index(&addr) {
+                Some(index) => Self::SPECIAL_PURPOSE_REGISTRY[index].4[2],
+                None => true,
+            },
+            Err(..) => false,
+        }
+    }
+    /// Check if a giving Ip address is reserved by a protocol for a special use
+    pub fn is_reserved_by_protocol(address: &str) -> bool {
+        match IpAddr::from_str(address) {
+            Ok(addr) => match Self::special_purpose_index(&addr) {
+                Some(index) => Self::SPECIAL_PURPOSE_REGISTRY[index].4[4],
+                None => false,
+            },
+            Err(..) => false,
         }
     }
     /// Returns the kind of a giving address
-    pub fn get_kind(address: &str) -> Result<IpKind, InvalidIpAddress> {
-        if IpKind::is_private(address) {
-            if IpVersion::is_v4(address) {
-                return Ok(IpKind::Private);
-            }
-            Ok(IpKind::Uniqelocal)
-        } else if IpKind::is_loopback(address) {
-            Ok(IpKind::Loopback)
-        } else if IpKind::is_multicast(address) {
-            Ok(IpKind::Multicast)
-        } else if IpKind::is_apipa(address) {
-            Ok(IpKind::Apipa)
-        } else if IpKind::is_linklocal(address) {
-            Ok(IpKind::Linklocal)
-        } else if IpKind::is_unspecified(address) {
-            Ok(IpKind::Unspecified)
-        } else if IpVersion::is_v4(address) {
-            Ok(IpKind::Public)
-        } else if IpVersion::is_v6(address) {
-            Ok(IpKind::Uniqeglobal)
-        } else {
-            Err(InvalidIpAddress)
-        }
-    }
-    pub fn get_broadcast(netid: &str, mask: &Mask) -> Result<IpAddress, InvalidIpAddress> {
-        if IpKind::is_netid(netid, mask) {
-            let max_hosts = mask.num_of_hosts();
-            let octats = IpAddress::octets_from_str(netid).unwrap();
-            let addr;
-            if *max_hosts < Mask::MAX_CLASS_C_ADDR as u32 {
-                addr = vec![
-                    octats[0],
-                    octats[1],
-                    octats[2],
-                    (max_hosts + octats[3] as u32 + 1) as u8,
-                ];
-            } else if *max_hosts < Mask::MAX_CLASS_B_ADDR {
-                let preportion = (max_hosts) / Mask::MAX_CLASS_C_ADDR as u32;
-                addr = vec![
-                    octats[0],
-                    octats[1],
-                    (octats[2] as u32 + preportion) as u8,
-                    255,
-                ];
-            } else if (*max_hosts as u64) < Mask::MAX_CLASS_A_ADDR {
-                let preportion = (max_hosts) / Mask::MAX_CLASS_B_ADDR;
-                addr = vec![octats[0], (octats[1] as u32 + preportion) as u8, 255, 255];
-            } else {
-                let preportion = ((*max_hosts) as u64) / Mask::MAX_CLASS_A_ADDR;
-                addr = vec![(octats[0] as u64 + preportion) as u8, 255, 255, 255];
-            }
-            return Ok(IpAddress {
-                address: addr,
-                version: IpVersion::V4,
-                kind: IpKind::Broadcast,
-            });
+    pub fn get_kind(address: &str) -> Result<IpKind, Error> {
+        let addr = IpAddr::from_str(address)
+            .map_err(|e| Error::new(InvalidIpAddress, address).with_source(e))?;
+        if addr.is_unspecified() {
+            return Ok(IpKind::Unspecified);
+        }
+        match Self::special_purpose_index(&addr) {
+            Some(index) => Ok(Self::SPECIAL_PURPOSE_REGISTRY[index].3.clone()),
+            None if addr.is_multicast() => Ok(IpKind::Multicast),
+            None if addr.is_ipv4() => Ok(IpKind::Public),
+            None => Ok(IpKind::Uniqeglobal),
+        }
+    }
+    /// Returns the broadcast of an Ipv4 network by its net id and subnet mask
+    pub fn get_broadcast(netid: &str, mask: &Mask) -> Result<IpAddress, Error> {
+        if IpKind::is_netid(netid, mask) && *mask.version() == IpVersion::V4 {
+            let id = IpAddress::from_str(netid)?;
+            let mut broadcast = IpAddress::from_u128(
+                id.as_u128() | Network::host_bits_mask(*mask.prefix(), mask.version()),
+                mask.version(),
+            )?;
+            broadcast.kind = IpKind::Broadcast;
+            return Ok(broadcast);
         }
-        Err(InvalidIpAddress)
+        Err(Error::new(
+            InvalidIpAddress,
+            format!("{netid}/{}", mask.prefix()),
+        ))
     }
 }
 
@@ -432,10 +926,62 @@ impl Display for IpKind {
             IpKind::Multicast => write!(f, "multicast"),
             IpKind::Unspecified => write!(f, "unspecified"),
             IpKind::Netid => write!(f, "netid"),
+            IpKind::Shared => write!(f, "shared"),
+            IpKind::Documentation => write!(f, "documentation"),
+            IpKind::Benchmarking => write!(f, "benchmarking"),
+            IpKind::Reserved => write!(f, "reserved"),
+            IpKind::SixToFour => write!(f, "6to4"),
+            IpKind::Teredo => write!(f, "teredo"),
+            IpKind::Ipv4Mapped => write!(f, "ipv4 mapped"),
+            IpKind::Orchid => write!(f, "orchid"),
+            IpKind::Special => write!(f, "special purpose"),
         }
     }
 }
 
+impl SpecialPurpose {
+    /// getter for the network of the registry entry
+    pub fn network(&self) -> &Network {
+        &self.network
+    }
+    /// getter for the name of the registry entry
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    /// getter for the RFC that defines the registry entry
+    pub fn rfc(&self) -> &String {
+        &self.rfc
+    }
+    /// getter for the address kind of the registry entry
+    pub fn kind(&self) -> &IpKind {
+        &self.kind
+    }
+    /// Checks if the addresses are valid as a source address
+    pub fn is_source(&self) -> bool {
+        self.source
+    }
+    /// Checks if the addresses are valid as a destination address
+    pub fn is_destination(&self) -> bool {
+        self.destination
+    }
+    /// Checks if routers may forward packets with the addresses
+    pub fn is_forwardable(&self) -> bool {
+        self.forwardable
+    }
+    /// Checks if the addresses are globally reachable
+    pub fn is_global(&self) -> bool {
+        self.global
+    }
+    /// Checks if the addresses are reserved by a protocol for a special use
+    pub fn is_reserved_by_protocol(&self) -> bool {
+        self.reserved_by_protocol
+    }
+    /// Checks if the registry entry is deprecated and kept only for reference
+    pub fn is_deprecated(&self) -> bool {
+        self.deprecated
+    }
+}
+
 impl IpAddress {
     pub const MAX_CLASS_C: u16 = 256;
     pub const MAX_CLASS_B: u32 = 65536;
@@ -444,38 +990,16 @@ impl IpAddress {
     pub fn is_valid(address: &str) -> bool {
         IpVersion::is_v4(address) || IpVersion::is_v6(address)
     }
-    /// Creates a new IpAddress instance
-    pub fn new(octets: &Vec<u8>) -> Result<IpAddress, InvalidIpAddress> {
-        if octets.len() != 6 && octets.len() != 16 {
-            return Err(InvalidIpAddress);
+    /// Creates a new IpAddress instance from 4 (Ipv4) or 16 (Ipv6) octets, any other length is
+    /// an invalid ip address
+    pub fn new(octets: &Vec<u8>) -> Result<IpAddress, Error> {
+        if let Ok(octets) = <[u8; 4]>::try_from(octets.as_slice()) {
+            Ok(Self::from(&IpAddr::V4(Ipv4Addr::from(octets))))
+        } else if let Ok(octets) = <[u8; 16]>::try_from(octets.as_slice()) {
+            Ok(Self::from(&IpAddr::V6(Ipv6Addr::from(octets))))
+        } else {
+            Err(Error::new(InvalidIpAddress, format!("{octets:?}")))
         }
-        Ok(IpAddress {
-            address: octets.clone(),
-            version: if octets.len() == 4 {
-                IpVersion::V4
-            } else {
-                IpVersion::V6
-            },
-            kind: if octets.len() == 4 {
-                IpKind::get_kind(
-                    &octets
-                        .iter()
-                        .map(|oct| oct.to_string())
-                        .collect::<Vec<String>>()
-                        .join("."),
-                )
-                .unwrap()
-            } else {
-                let mut address = String::new();
-                let mut i = 0;
-                while i < octets.len() - 1 {
-                    address = format!("{address}{}{}:", octets[i], octets[i + 1]);
-                    i += 2;
-                }
-                address.pop();
-                IpKind::get_kind(&address).unwrap()
-            },
-        })
     }
     /// Creates a new IpAddress instance from IpAddr
     pub fn from(address: &IpAddr) -> IpAddress {
@@ -517,7 +1041,7 @@ impl IpAddress {
             .collect::<Vec<IpAddress>>()
     }
     /// Get the octats values of an ip address as u8 vector from giving &str
-    pub fn octets_from_str(address: &str) -> Result<Vec<u8>, InvalidIpAddress> {
+    pub fn octets_from_str(address: &str) -> Result<Vec<u8>, Error> {
         if IpVersion::is_v6(address) {
             let addr: Ipv6Addr = address.parse().unwrap();
             Ok(addr.octets().to_vec())
@@ -525,15 +1049,72 @@ impl IpAddress {
             let octets: Ipv4Addr = address.parse().unwrap();
             Ok(octets.octets().to_vec())
         } else {
-            Err(InvalidIpAddress)
+            Err(Error::new(InvalidIpAddress, address))
         }
     }
     /// Get the octets values of an ipv4 IpAddress instance
     pub fn octets(&self) -> &Vec<u8> {
         &self.address
     }
+    /// Returns the address as an unsigned integer
+    pub fn as_u128(&self) -> u128 {
+        self.address
+            .iter()
+            .fold(0u128, |value, oct| (value << 8) | *oct as u128)
+    }
+    /// Creates a new IpAddress instance from an unsigned integer of the giving version
+    pub fn from_u128(value: u128, version: &IpVersion) -> Result<IpAddress, Error> {
+        match version {
+            IpVersion::V4 => match u32::try_from(value) {
+                Ok(value) => Ok(Self::from(&IpAddr::V4(Ipv4Addr::from(value)))),
+                Err(_) => Err(Error::new(InvalidIpAddress, value)),
+            },
+            IpVersion::V6 => Ok(Self::from(&IpAddr::V6(Ipv6Addr::from(value)))),
+        }
+    }
+    /// Returns the address that is `offset` addresses after self
+    pub fn checked_add(&self, offset: u128) -> Result<IpAddress, Error> {
+        let overflow = || Error::new(AddressOverflow, format!("{self} + {offset}"));
+        match self.as_u128().checked_add(offset) {
+            Some(value) => Self::from_u128(value, &self.version).map_err(|_| overflow()),
+            None => Err(overflow()),
+        }
+    }
+    /// Returns the address that is `offset` addresses before self
+    pub fn checked_sub(&self, offset: u128) -> Result<IpAddress, Error> {
+        let overflow = || Error::new(AddressOverflow, format!("{self} - {offset}"));
+        match self.as_u128().checked_sub(offset) {
+            Some(value) => Self::from_u128(value, &self.version).map_err(|_| overflow()),
+            None => Err(overflow()),
+        }
+    }
+    /// Returns the address right after self
+    pub fn next(&self) -> Result<IpAddress, Error> {
+        self.checked_add(1)
+    }
+    /// Returns the address right before self
+    pub fn prev(&self) -> Result<IpAddress, Error> {
+        self.checked_sub(1)
+    }
+    /// Returns the number of addresses from self to a giving address of the same version,
+    /// negative if the giving address comes before self, an address of another version is an
+    /// invalid ip address
+    pub fn distance(&self, other: &IpAddress) -> Result<i128, Error> {
+        if self.version != other.version {
+            return Err(Error::new(InvalidIpAddress, other));
+        }
+        let overflow = || Error::new(AddressOverflow, format!("{self} - {other}"));
+        let (from, to) = (self.as_u128(), other.as_u128());
+        if to >= from {
+            i128::try_from(to - from).map_err(|_| overflow())
+        } else {
+            i128::try_from(from - to)
+                .map(|distance| -distance)
+                .map_err(|_| overflow())
+        }
+    }
     /// Get the ipv6 address as expended
-    pub fn get_expended(&self) -> Result<String, InvalidIpV6Address> {
+    pub fn get_expended(&self) -> Result<String, Error> {
         Self::expend(&self.address())
     }
     // Getters for the IpAddress properties
@@ -576,8 +1157,291 @@ impl IpAddress {
             address,
         }
     }
+    /// Returns the Ipv4-mapped Ipv6 address (`::ffff:a.b.c.d`) of an Ipv4 address
+    pub fn to_ipv4_mapped(&self) -> Result<IpAddress, Error> {
+        let ipv4 = self.ipv4_value()?;
+        IpAddress::from_u128((0xffff << 32) | ipv4, &IpVersion::V6)
+    }
+    /// Returns the Ipv4 address of an Ipv4-mapped Ipv6 address
+    pub fn extract_ipv4_mapped(&self) -> Result<IpAddress, Error> {
+        let address = self.ipv6_value()?;
+        if address >> 32 != 0xffff {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        IpAddress::from_u128(address & 0xffff_ffff, &IpVersion::V4)
+    }
+    /// Embeds an Ipv4 address in a NAT64 prefix (RFC 6052), the prefix length has to be 32, 40,
+    /// 48, 56, 64 or 96, bits 64 to 71 of the prefix have to be zero and are left zero
+    pub fn to_nat64(&self, prefix: &Network) -> Result<IpAddress, Error> {
+        let ipv4 = self.ipv4_value()?;
+        let length = Self::nat64_prefix(prefix)?;
+        let embedded = match length {
+            96 => ipv4,
+            64 => ipv4 << 24,
+            _ => {
+                // The address is split around the reserved `u` octet (bits 64 to 71)
+                let high_bits = 64 - length as u32;
+                let low_bits = 32 - high_bits;
+                ((ipv4 >> low_bits) << 64) | ((ipv4 & ((1 << low_bits) - 1)) << (24 + high_bits))
+            }
+        };
+        IpAddress::from_u128(prefix.netid().as_u128() | embedded, &IpVersion::V6)
+    }
+    /// Returns the Ipv4 address that is embedded in an Ipv6 address with a giving NAT64 prefix
+    pub fn extract_nat64(&self, prefix: &Network) -> Result<IpAddress, Error> {
+        let address = self.ipv6_value()?;
+        let length = Self::nat64_prefix(prefix)?;
+        if !prefix.contains(self) || (address >> 56) & 0xff != 0 {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        let ipv4 = match length {
+            96 => address & 0xffff_ffff,
+            64 => (address >> 24) & 0xffff_ffff,
+            _ => {
+                let high_bits = 64 - length as u32;
+                let low_bits = 32 - high_bits;
+                let high = (address >> 64) & ((1 << high_bits) - 1);
+                let low = (address >> (24 + high_bits)) & ((1 << low_bits) - 1);
+                (high << low_bits) | low
+            }
+        };
+        IpAddress::from_u128(ipv4, &IpVersion::V4)
+    }
+    /// Returns the 6to4 network (`2002:wwxx:yyzz::/48`) of an Ipv4 address
+    pub fn to_6to4(&self) -> Result<Network, Error> {
+        let ipv4 = self.ipv4_value()?;
+        Ok(Network::from_u128(
+            (0x2002 << 112) | (ipv4 << 80),
+            48,
+            &IpVersion::V6,
+        ))
+    }
+    /// Returns the Ipv4 address of a 6to4 address
+    pub fn extract_6to4(&self) -> Result<IpAddress, Error> {
+        let address = self.ipv6_value()?;
+        if address >> 112 != 0x2002 {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        IpAddress::from_u128((address >> 80) & 0xffff_ffff, &IpVersion::V4)
+    }
+    /// Returns the ISATAP address (RFC 5214) of an Ipv4 address in a giving /64 Ipv6 prefix, the
+    /// universal/local bit of the interface id is set when the Ipv4 address is public
+    pub fn to_isatap(&self, prefix: &Network) -> Result<IpAddress, Error> {
+        let ipv4 = self.ipv4_value()?;
+        if *prefix.netid().version() != IpVersion::V6 || *prefix.mask().prefix() != 64 {
+            return Err(Error::new(InvalidNetwork, prefix));
+        }
+        let universal = match IpKind::is_public(&self.to_string()) {
+            true => 0x0200 << 48,
+            false => 0,
+        };
+        IpAddress::from_u128(
+            prefix.netid().as_u128() | universal | (0x5efe << 32) | ipv4,
+            &IpVersion::V6,
+        )
+    }
+    /// Returns the Ipv4 address of an ISATAP address, the group bit of the interface id has to
+    /// be clear and the universal/local bit has to match the Ipv4 address being public
+    pub fn extract_isatap(&self) -> Result<IpAddress, Error> {
+        let address = self.ipv6_value()?;
+        if (address >> 32) & 0xfcff_ffff != 0x5efe {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        let ipv4 = IpAddress::from_u128(address & 0xffff_ffff, &IpVersion::V4)?;
+        let universal = (address >> 57) & 1 == 1;
+        let group = (address >> 56) & 1 == 1;
+        if group || universal != IpKind::is_public(&ipv4.to_string()) {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        Ok(ipv4)
+    }
+    /// Returns the solicited-node multicast address (`ff02::1:ffxx:xxxx`) of an Ipv6 address
+    pub fn solicited_node(&self) -> Result<IpAddress, Error> {
+        let address = self.ipv6_value()?;
+        IpAddress::from_u128(
+            (0xff02 << 112) | (0x1_ff00_0000) | (address & 0xff_ffff),
+            &IpVersion::V6,
+        )
+    }
+    /// Returns the Ethernet address that a multicast address is mapped to, `33:33:xx:xx:xx:xx` for
+    /// Ipv6 and `01:00:5e:xx:xx:xx` for Ipv4
+    pub fn multicast_mac(&self) -> Result<MacAddress, Error> {
+        if !IpKind::is_multicast(&self.to_string()) {
+            return Err(Error::new(InvalidIpAddress, self));
+        }
+        let octets = &self.address;
+        let length = octets.len();
+        Ok(MacAddress::new(match self.version {
+            IpVersion::V4 => [0x01, 0x00, 0x5e, octets[1] & 0x7f, octets[2], octets[3]],
+            IpVersion::V6 => [
+                0x33,
+                0x33,
+                octets[length - 4],
+                octets[length - 3],
+                octets[length - 2],
+                octets[length - 1],
+            ],
+        }))
+    }
+    /// Creates a stable, semantically opaque address in a giving Ipv6 prefix (RFC 7217).
+    /// The interface id is the SHA-256 digest of the prefix, the interface name, the network id,
+    /// the DAD counter and the secret key, the counter is increased while the id is reserved and
+    /// there is no address left once it passes 255
+    pub fn stable_privacy(
+        prefix: &Network,
+        interface: &str,
+        network_id: &str,
+        dad_counter: u8,
+        secret: &[u8],
+    ) -> Result<IpAddress, Error> {
+        if *prefix.netid().version() != IpVersion::V6 {
+            return Err(Error::new(InvalidNetwork, prefix));
+        }
+        for dad_counter in dad_counter..=u8::MAX {
+            let mut data = prefix.netid().octets().clone();
+            data.extend_from_slice(interface.as_bytes());
+            data.extend_from_slice(network_id.as_bytes());
+            data.push(dad_counter);
+            data.extend_from_slice(secret);
+            let address = Self::from_digest(prefix, &Sha256::digest(&data));
+            if !Self::is_reserved_iid(prefix, &address) {
+                return Ok(address);
+            }
+        }
+        Err(Error::new(InsufficientAddressSpace, prefix))
+    }
+    /// Creates a temporary address in a giving Ipv6 prefix (RFC 8981, the update of RFC 4941).
+    /// The interface id is the SHA-256 digest of the prefix, the current time and the secret
+    /// key so every call returns a new address, a reserved id is regenerated up to 255 times
+    pub fn temporary(prefix: &Network, secret: &[u8]) -> Result<IpAddress, Error> {
+        if *prefix.netid().version() != IpVersion::V6 {
+            return Err(Error::new(InvalidNetwork, prefix));
+        }
+        let time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos();
+        for counter in 0..=u8::MAX {
+            let mut data = prefix.netid().octets().clone();
+            data.extend_from_slice(&time.to_be_bytes());
+            data.push(counter);
+            data.extend_from_slice(secret);
+            let address = Self::from_digest(prefix, &Sha256::digest(&data));
+            if !Self::is_reserved_iid(prefix, &address) {
+                return Ok(address);
+            }
+        }
+        Err(Error::new(InsufficientAddressSpace, prefix))
+    }
+    /// Fills the host bits of a prefix with the least significant bits of a digest
+    fn from_digest(prefix: &Network, digest: &[u8]) -> IpAddress {
+        let host_bits = Network::host_bits_mask(*prefix.mask().prefix(), &IpVersion::V6);
+        let iid = u128::from_be_bytes(digest[16..].try_into().unwrap()) & host_bits;
+        IpAddress::from_u128(prefix.netid().as_u128() | iid, &IpVersion::V6).unwrap()
+    }
+    /// Checks if the interface id of an address in a /64 prefix is reserved (RFC 5453)
+    fn is_reserved_iid(prefix: &Network, address: &IpAddress) -> bool {
+        let iid = address.as_u128() as u64;
+        *prefix.mask().prefix() == 64
+            && (iid == 0
+                || (0x0200_5eff_fe00_0000..=0x0200_5eff_feff_ffff).contains(&iid)
+                || iid >= 0xfdff_ffff_ffff_ff80)
+    }
+    /// Returns the integer value of an Ipv4 address
+    fn ipv4_value(&self) -> Result<u128, Error> {
+        match self.version {
+            IpVersion::V4 => Ok(self.as_u128()),
+            IpVersion::V6 => Err(Error::new(InvalidIpAddress, self)),
+        }
+    }
+    /// Returns the integer value of an Ipv6 address
+    fn ipv6_value(&self) -> Result<u128, Error> {
+        match self.version {
+            IpVersion::V6 => Ok(self.as_u128()),
+            IpVersion::V4 => Err(Error::new(InvalidIpAddress, self)),
+        }
+    }
+    /// Returns the length of a valid NAT64 prefix
+    fn nat64_prefix(prefix: &Network) -> Result<u8, Error> {
+        let length = *prefix.mask().prefix();
+        if *prefix.netid().version() != IpVersion::V6 || ![32, 40, 48, 56, 64, 96].contains(&length)
+        {
+            return Err(Error::new(InvalidNetwork, prefix));
+        }
+        // The `u` octet (bits 64 to 71) is reserved and has to be zero
+        if (prefix.netid().as_u128() >> 56) & 0xff != 0 {
+            return Err(Error::new(InvalidNetwork, prefix));
+        }
+        Ok(length)
+    }
+    /// Returns the reverse DNS (PTR) name of the address in the `in-addr.arpa` or `ip6.arpa`
+    /// domain
+    pub fn reverse_pointer(&self) -> String {
+        let labels = match self.version {
+            IpVersion::V4 => 4,
+            IpVersion::V6 => 32,
+        };
+        Self::reverse_name(self.as_u128(), &self.version, labels)
+    }
+    /// Parses a reverse DNS (PTR) name of a single address back into an IpAddress instance
+    pub fn from_reverse_pointer(name: &str) -> Result<IpAddress, Error> {
+        let invalid = || Error::new(InvalidIpAddress, name);
+        let labels = name.trim_end_matches('.').to_lowercase();
+        if let Some(labels) = labels.strip_suffix(".in-addr.arpa") {
+            let octets = labels
+                .rsplit('.')
+                .map(|label| match label.chars().all(|c| c.is_ascii_digit()) {
+                    true => label.parse::<u8>().ok(),
+                    false => None,
+                })
+                .collect::<Option<Vec<u8>>>()
+                .ok_or_else(invalid)?;
+            if octets.len() != 4 {
+                return Err(invalid());
+            }
+            IpAddress::new(&octets)
+        } else if let Some(labels) = labels.strip_suffix(".ip6.arpa") {
+            let nibbles = labels
+                .rsplit('.')
+                .map(|label| match label.len() {
+                    1 => u128::from_str_radix(label, 16).ok(),
+                    _ => None,
+                })
+                .collect::<Option<Vec<u128>>>()
+                .ok_or_else(invalid)?;
+            if nibbles.len() != 32 {
+                return Err(invalid());
+            }
+            let value = nibbles
+                .iter()
+                .fold(0u128, |value, nibble| (value << 4) | nibble);
+            IpAddress::from_u128(value, &IpVersion::V6)
+        } else {
+            Err(invalid())
+        }
+    }
+    /// Returns the reverse DNS name of the first octets (Ipv4) or nibbles (Ipv6) of an address
+    fn reverse_name(value: u128, version: &IpVersion, labels: u8) -> String {
+        let (label_bits, domain) = match version {
+            IpVersion::V4 => (8, "in-addr.arpa"),
+            IpVersion::V6 => (4, "ip6.arpa"),
+        };
+        let mut name = (0..labels)
+            .rev()
+            .map(|i| {
+                let label =
+                    (value >> (version.bits() - label_bits * (i + 1))) & ((1 << label_bits) - 1);
+                match version {
+                    IpVersion::V4 => label.to_string(),
+                    IpVersion::V6 => format!("{:x}", label),
+                }
+            })
+            .collect::<Vec<String>>();
+        name.push(domain.to_string());
+        name.join(".")
+    }
     /// Expends a giving ipv6 address
-    pub fn expend(address: &str) -> Result<String, InvalidIpV6Address> {
+    pub fn expend(address: &str) -> Result<String, Error> {
         if IpVersion::is_v6(address) {
             let mut exp_addr = String::new();
             let parts = address.split(":").collect::<Vec<&str>>();
@@ -605,53 +1469,49 @@ impl IpAddress {
             }
             Ok(exp_addr)
         } else {
-            Err(InvalidIpV6Address)
+            Err(Error::new(InvalidIpV6Address, address))
         }
     }
-    /// Shorten a giving ipv6 address
-    pub fn shorten(address: &str) -> Result<String, InvalidIpV6Address> {
-        if IpVersion::is_v6(address) {
-            let octets = address.parse::<Ipv6Addr>().unwrap().segments();
-            let mut max_zeros: usize = 0;
-            let mut max_zeros_index = 0;
-            let mut curr_zeros = 0;
-            let mut curr_zeros_index = 0;
-            let mut is_leading = true;
-            let mut shorten_addr = String::new();
-            for (i, oct) in octets.clone().into_iter().enumerate() {
-                if oct == 0 {
-                    if is_leading {
-                        is_leading = false;
-                        curr_zeros_index = i;
-                    }
-                    curr_zeros += 1;
-                } else {
-                    is_leading = true;
-                    if curr_zeros > max_zeros {
-                        max_zeros = curr_zeros;
-                        max_zeros_index = curr_zeros_index;
-                    }
-                    curr_zeros = 0;
-                }
+    /// Shorten a giving ipv6 address, the first longest run of two or more zero segments is
+    /// replaced with `::` even at the start or the end of the address
+    pub fn shorten(address: &str) -> Result<String, Error> {
+        if !IpVersion::is_v6(address) {
+            return Err(Error::new(InvalidIpV6Address, address));
+        }
+        let segments = address.parse::<Ipv6Addr>().unwrap().segments();
+        // Finding the first longest run of zero segments
+        let mut zeros_index = 0;
+        let mut zeros_len = 0;
+        let mut i = 0;
+        while i < segments.len() {
+            let start = i;
+            while i < segments.len() && segments[i] == 0 {
+                i += 1;
             }
-            for i in 0..((octets.len()) as usize) {
-                if i >= max_zeros_index && i < max_zeros_index + max_zeros {
-                    continue;
-                }
-                shorten_addr = format!("{}{:x}", shorten_addr, octets[i]);
-                if i != octets.len() - 1 {
-                    if i == max_zeros_index - 1 {
-                        shorten_addr.push_str("::");
-                    } else {
-                        shorten_addr.push_str(":");
-                    }
-                }
+            if i - start > zeros_len {
+                zeros_index = start;
+                zeros_len = i - start;
             }
-            Ok(shorten_addr)
-        } else {
-            Err(InvalidIpV6Address)
+            i += 1;
         }
-    }
+        let join = |segments: &[u16]| {
+            segments
+                .iter()
+                .map(|seg| format!("{:x}", seg))
+                .collect::<Vec<String>>()
+                .join(":")
+        };
+        // A single zero segment is not shortened
+        if zeros_len < 2 {
+            Ok(join(&segments))
+        } else {
+            Ok(format!(
+                "{}::{}",
+                join(&segments[..zeros_index]),
+                join(&segments[zeros_index + zeros_len..])
+            ))
+        }
+    }
 }
 
 impl Display for IpAddress {
@@ -682,8 +1542,65 @@ impl Display for IpAddress {
     }
 }
 
+impl PartialEq for IpAddress {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.address == other.address
+    }
+}
+
+impl Eq for IpAddress {}
+
+impl Hash for IpAddress {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.version.hash(state);
+        self.address.hash(state);
+    }
+}
+
+impl PartialOrd for IpAddress {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for IpAddress {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.version
+            .cmp(&other.version)
+            .then_with(|| self.address.cmp(&other.address))
+    }
+}
+
+impl Add<u128> for &IpAddress {
+    type Output = Result<IpAddress, Error>;
+    fn add(self, offset: u128) -> Self::Output {
+        self.checked_add(offset)
+    }
+}
+
+impl Add<u128> for IpAddress {
+    type Output = Result<IpAddress, Error>;
+    fn add(self, offset: u128) -> Self::Output {
+        self.checked_add(offset)
+    }
+}
+
+impl Sub<u128> for &IpAddress {
+    type Output = Result<IpAddress, Error>;
+    fn sub(self, offset: u128) -> Self::Output {
+        self.checked_sub(offset)
+    }
+}
+
+impl Sub<u128> for IpAddress {
+    type Output = Result<IpAddress, Error>;
+    fn sub(self, offset: u128) -> Self::Output {
+        self.checked_sub(offset)
+    }
+}
+
 impl FromStr for IpAddress {
-    type Err = InvalidIpAddress;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if IpAddress::is_valid(s) {
             let addr = IpAddress::octets_from_str(s)?;
@@ -697,99 +1614,109 @@ impl FromStr for IpAddress {
                 kind: IpKind::get_kind(s)?,
             });
         } else {
-            Err(InvalidIpAddress)
+            Err(Error::new(InvalidIpAddress, s))
         }
     }
 }
 
 impl Mask {
-    const MAX_CLASS_A_ADDR: u64 = 16777216;
-    const MAX_CLASS_B_ADDR: u32 = 65536;
-    const MAX_CLASS_C_ADDR: u16 = 256;
-
     /// Checks if a giving Subnet Mask is valid
     pub fn is_valid(mask: &str) -> bool {
-        let octats_values: Vec<u8> = IpAddress::octets_from_str(mask).unwrap_or(vec![]);
-        if octats_values.len() == 0 {
-            return false;
+        match IpAddress::octets_from_str(mask) {
+            Ok(octets) => Self::contiguous_bits(&octets).is_some(),
+            Err(_) => false,
+        }
+    }
+    /// Returns the mask bits aligned to the most significant bit if they are contiguous
+    fn contiguous_bits(octets: &[u8]) -> Option<u128> {
+        if octets.len() != 4 && octets.len() != 16 {
+            return None;
+        }
+        let value = octets
+            .iter()
+            .fold(0u128, |value, oct| (value << 8) | *oct as u128)
+            << (128 - octets.len() * 8);
+        if value.leading_ones() + value.trailing_zeros() == 128 {
+            Some(value)
+        } else {
+            None
         }
-        let mask_value = (octats_values[0] as u32) << 24
-            | (octats_values[1] as u32) << 16
-            | (octats_values[2] as u32) << 8
-            | octats_values[3] as u32;
-        let ones_count = mask_value.leading_ones();
-        u32::MAX << 32 - ones_count == mask_value as u32
     }
     /// Returns the prefix of a giving address
     pub fn get_prefix(octets_values: &Vec<u8>) -> u8 {
-        let mask_value = (octets_values[0] as u32) << 24
-            | (octets_values[1] as u32) << 16
-            | (octets_values[2] as u32) << 8
-            | octets_values[3] as u32;
-        mask_value.leading_ones() as u8
-    }
-    /// Creates a new Mask instance
-    pub fn new(bytes: &Vec<u8>) -> Result<Mask, InvalidMask> {
-        if bytes.len() == 4 || bytes.len() == 16 {
-            let prefix = Mask::get_prefix(bytes);
-            Ok(Mask {
-                prefix,
-                num_of_hosts: (2 as u32).pow(32 - prefix as u32) - 2,
-            })
-        } else {
-            Err(InvalidMask)
+        let bits = (octets_values.len().min(16) * 8) as u32;
+        let value = octets_values
+            .iter()
+            .take(16)
+            .fold(0u128, |value, oct| (value << 8) | *oct as u128);
+        value.checked_shl(128 - bits).unwrap_or(0).leading_ones() as u8
+    }
+    /// Creates a new Mask instance, 4 bytes for an Ipv4 mask and 16 bytes for an Ipv6 mask
+    pub fn new(bytes: &Vec<u8>) -> Result<Mask, Error> {
+        if Self::contiguous_bits(bytes).is_none() {
+            return Err(Error::new(InvalidMask, format!("{bytes:?}")));
         }
+        let version = if bytes.len() == 4 {
+            IpVersion::V4
+        } else {
+            IpVersion::V6
+        };
+        Self::from_version_prefix(Mask::get_prefix(bytes), &version)
+    }
+    /// Creates new Ipv4 Mask instance from giving prefix
+    pub fn from_prefix(prefix: u8) -> Result<Mask, Error> {
+        Self::from_version_prefix(prefix, &IpVersion::V4)
     }
-    /// Creates new Mask instance from giving prefix
-    pub fn from_prefix(prefix: u8) -> Result<Mask, InvalidPrefix> {
-        if prefix > 32 {
-            return Err(InvalidPrefix);
+    /// Creates new Ipv6 Mask instance from giving prefix
+    pub fn from_prefix_v6(prefix: u8) -> Result<Mask, Error> {
+        Self::from_version_prefix(prefix, &IpVersion::V6)
+    }
+    /// Creates new Mask instance from giving prefix and Ip version
+    pub fn from_version_prefix(prefix: u8, version: &IpVersion) -> Result<Mask, Error> {
+        if prefix > version.bits() {
+            return Err(Error::new(InvalidPrefix, prefix));
         }
         Ok(Mask {
             prefix,
-            num_of_hosts: if prefix == 0 {
-                u32::MAX
-            } else {
-                2u32.pow(32 - prefix as u32) - 2
-            },
+            num_of_hosts: Network::usable_hosts(version.bits() - prefix, version),
+            version: version.clone(),
         })
     }
+    /// Returns the mask as an unsigned integer
+    fn as_u128(&self) -> u128 {
+        let full = match self.version {
+            IpVersion::V4 => u32::MAX as u128,
+            IpVersion::V6 => u128::MAX,
+        };
+        full & !Network::host_bits_mask(self.prefix, &self.version)
+    }
 
     pub fn mask(&self) -> String {
-        let full_bytes: u32 = u32::MAX;
-        let mask_bytes = if self.prefix == 0 {
-            0
-        } else {
-            full_bytes << 32 - self.prefix
-        };
-        let octats = mask_bytes.to_ne_bytes();
-        format!("{}.{}.{}.{}", octats[3], octats[2], octats[1], octats[0])
+        IpAddress::from_u128(self.as_u128(), &self.version)
+            .unwrap()
+            .to_string()
     }
 
     pub fn wildcard(&self) -> String {
-        let full_bytes: u32 = u32::MAX;
-        let mask_bytes = if self.prefix == 0 {
-            0
-        } else {
-            full_bytes << 32 - self.prefix
-        };
-        let octats = mask_bytes.to_ne_bytes();
-        format!(
-            "{}.{}.{}.{}",
-            255 - octats[3],
-            255 - octats[2],
-            255 - octats[1],
-            255 - octats[0]
+        IpAddress::from_u128(
+            Network::host_bits_mask(self.prefix, &self.version),
+            &self.version,
         )
+        .unwrap()
+        .to_string()
     }
 
     pub fn prefix(&self) -> &u8 {
         &self.prefix
     }
 
-    pub fn num_of_hosts(&self) -> &u32 {
+    pub fn num_of_hosts(&self) -> &u128 {
         &self.num_of_hosts
     }
+
+    pub fn version(&self) -> &IpVersion {
+        &self.version
+    }
 }
 
 impl Display for Mask {
@@ -799,57 +1726,250 @@ impl Display for Mask {
 }
 
 impl FromStr for Mask {
-    type Err = InvalidMask;
+    type Err = Error;
     fn from_str(mask: &str) -> Result<Self, Self::Err> {
-        if Mask::is_valid(mask) {
-            let prefix = Mask::get_prefix(&IpAddress::octets_from_str(mask).unwrap());
-            return Ok(Mask {
-                prefix,
-                num_of_hosts: (2 as u32).pow(32 - prefix as u32) - 2,
-            });
+        IpAddress::octets_from_str(mask)
+            .and_then(|octets| Mask::new(&octets))
+            .map_err(|_| Error::new(InvalidMask, mask))
+    }
+}
+
+impl WildcardMask {
+    /// Creates a new WildcardMask instance, 4 bytes for an Ipv4 wildcard and 16 bytes for an
+    /// Ipv6 wildcard
+    pub fn new(bytes: &[u8]) -> Result<WildcardMask, Error> {
+        let version = match bytes.len() {
+            4 => IpVersion::V4,
+            16 => IpVersion::V6,
+            _ => return Err(Error::new(InvalidWildcardMask, format!("{bytes:?}"))),
+        };
+        Ok(WildcardMask {
+            bits: bytes
+                .iter()
+                .fold(0u128, |value, oct| (value << 8) | *oct as u128),
+            version,
+        })
+    }
+    /// Checks if a giving wildcard mask is valid
+    pub fn is_valid(wildcard: &str) -> bool {
+        WildcardMask::from_str(wildcard).is_ok()
+    }
+    /// Checks if the ignored bits are all at the end of the wildcard so it has an equivalent
+    /// subnet mask
+    pub fn is_contiguous(&self) -> bool {
+        self.bits & self.bits.wrapping_add(1) == 0
+    }
+    /// Returns the equivalent subnet mask of a contiguous wildcard
+    pub fn to_mask(&self) -> Result<Mask, Error> {
+        if !self.is_contiguous() {
+            return Err(Error::new(InvalidMask, self));
         }
-        Err(InvalidMask)
+        let prefix = self.version.bits() - (128 - self.bits.leading_zeros()) as u8;
+        Mask::from_version_prefix(prefix, &self.version)
+    }
+    /// Checks if a giving address matches an `address wildcard` pair, the bits of the addresses
+    /// have to be equal everywhere the wildcard bits are not set
+    pub fn matches(&self, base: &IpAddress, address: &IpAddress) -> bool {
+        base.version() == &self.version
+            && address.version() == &self.version
+            && (base.as_u128() ^ address.as_u128()) & !self.bits == 0
+    }
+    /// Returns the number of addresses that match the wildcard with any base address
+    pub fn num_of_addresses(&self) -> u128 {
+        1u128
+            .checked_shl(self.bits.count_ones())
+            .unwrap_or(u128::MAX)
+    }
+    /// getter for the wildcard bits as an octets vector
+    pub fn octets(&self) -> Vec<u8> {
+        IpAddress::from_u128(self.bits, &self.version)
+            .unwrap()
+            .octets()
+            .clone()
+    }
+
+    pub fn version(&self) -> &IpVersion {
+        &self.version
+    }
+}
+
+impl From<&Mask> for WildcardMask {
+    fn from(mask: &Mask) -> Self {
+        WildcardMask {
+            bits: Network::host_bits_mask(mask.prefix, &mask.version),
+            version: mask.version.clone(),
+        }
+    }
+}
+
+impl Display for WildcardMask {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            IpAddress::from_u128(self.bits, &self.version).unwrap()
+        )
+    }
+}
+
+impl FromStr for WildcardMask {
+    type Err = Error;
+    fn from_str(wildcard: &str) -> Result<Self, Self::Err> {
+        IpAddress::octets_from_str(wildcard)
+            .and_then(|octets| WildcardMask::new(&octets))
+            .map_err(|_| Error::new(InvalidWildcardMask, wildcard))
     }
 }
 
 impl Network {
-    /// Creates a new ipv4 Network instance from giving net id and subnet mask
-    pub fn new(id: IpAddress, mask: Mask) -> Result<Network, InvalidNetwork> {
-        if IpKind::is_netid(id.address().as_str(), &mask) {
-            if IpVersion::is_v4(&id.address().as_str()) {
-                return Ok(Network {
-                    broadcast: IpKind::get_broadcast(id.address().as_str(), &mask).unwrap(),
-                    mask,
-                    id,
-                });
-            }
+    /// Creates a new Network instance from giving net id and subnet mask
+    pub fn new(id: IpAddress, mask: Mask) -> Result<Network, Error> {
+        if id.version() != mask.version()
+            || id.as_u128() & Self::host_bits_mask(*mask.prefix(), mask.version()) != 0
+        {
+            return Err(Error::new(
+                InvalidNetwork,
+                format!("{id}/{}", mask.prefix()),
+            ));
+        }
+        Ok(Network::from_u128(
+            id.as_u128(),
+            *mask.prefix(),
+            mask.version(),
+        ))
+    }
+    /// Parses a network written in any of the `id/prefix`, `id/mask`, `id mask`, `id wildcard`
+    /// or `id prefix` formats. In strict mode an address with host bits set is rejected,
+    /// otherwise the host bits are cleared, so `10.0.0.5/24` is parsed as `10.0.0.0/24`
+    pub fn parse(s: &str, strict: bool) -> Result<Network, Error> {
+        let invalid = |source| Error::new(InvalidNetwork, s).with_source(source);
+        let (address, mask) = Self::parse_address_mask(s).map_err(invalid)?;
+        if strict {
+            Network::new(address, mask).map_err(invalid)
+        } else {
+            Ok(Network::from_u128(
+                address.as_u128() & mask.as_u128(),
+                *mask.prefix(),
+                mask.version(),
+            ))
+        }
+    }
+    /// Splits an address and its mask written in any of the formats `Network::parse` supports, a
+    /// dotted value is taken as a subnet mask if it is one and as a wildcard mask otherwise. The
+    /// all-zero and all-ones values are both a subnet and a wildcard mask, so they are rejected
+    /// and the prefix has to be used instead
+    fn parse_address_mask(s: &str) -> Result<(IpAddress, Mask), Error> {
+        let s = s.trim();
+        let (address, mask) = s
+            .split_once('/')
+            .or_else(|| s.split_once(char::is_whitespace))
+            .ok_or_else(|| Error::new(InvalidMask, ""))?;
+        let address = IpAddress::from_str(address.trim())?;
+        let mask = mask.trim();
+        let mask = match mask.parse::<u8>() {
+            Ok(prefix) => Mask::from_version_prefix(prefix, address.version())?,
+            Err(_) => match Mask::from_str(mask) {
+                Ok(subnet)
+                    if *subnet.prefix() == 0 || *subnet.prefix() == subnet.version().bits() =>
+                {
+                    return Err(Error::new(InvalidMask, mask));
+                }
+                Ok(subnet) => subnet,
+                Err(_) => WildcardMask::from_str(mask)
+                    .and_then(|wildcard| wildcard.to_mask())
+                    .map_err(|_| Error::new(InvalidMask, mask))?,
+            },
+        };
+        if mask.version() != address.version() {
+            return Err(Error::new(InvalidMask, mask));
         }
-        Err(InvalidNetwork)
+        Ok((address, mask))
     }
-    /// Checks if a giving Ip address is in the self network
+    /// Checks if a giving Ip address is in the self network, every address from the net id to
+    /// the broadcast is, so for Ipv4 the net id and the broadcast are in the network as well
     pub fn contains(&self, address: &IpAddress) -> bool {
-        if IpVersion::is_v4(address.address().as_str()) {
-            let octats = address.octets();
-            let netid_octs = self.id.octets();
-            let bcast_octs = self.broadcast.octets();
-            let prefix = self.mask.prefix();
-            if *prefix >= 24 {
-                octats[3] > netid_octs[3] && octats[3] < bcast_octs[3]
-            } else if *prefix >= 16 {
-                octats[2] <= bcast_octs[2] && octats[3] > netid_octs[3] && octats[3] < bcast_octs[3]
-            } else if *prefix >= 8 {
-                octats[1] <= bcast_octs[1] && octats[3] > netid_octs[3] && octats[3] < bcast_octs[3]
+        let (first, last) = self.bounds();
+        address.version() == self.id.version()
+            && address.as_u128() >= first
+            && address.as_u128() <= last
+    }
+    /// Checks if the self network and a giving network have any address in common
+    pub fn overlaps(&self, other: &Network) -> bool {
+        let (first, last) = self.bounds();
+        let (other_first, other_last) = other.bounds();
+        self.id.version() == other.id.version() && first <= other_last && other_first <= last
+    }
+    /// Checks if the self network is inside a giving network (a network is a subnet of itself)
+    pub fn is_subnet_of(&self, other: &Network) -> bool {
+        self.mask.prefix >= other.mask.prefix && other.contains(&self.id)
+    }
+    /// Checks if a giving network is inside the self network (a network is a supernet of itself)
+    pub fn is_supernet_of(&self, other: &Network) -> bool {
+        other.is_subnet_of(self)
+    }
+    /// Returns the networks that remain after removing the addresses of a giving network from the
+    /// self network, sorted by address
+    pub fn exclude(&self, other: &Network) -> Vec<Network> {
+        if !self.overlaps(other) {
+            return vec![self.clone()];
+        }
+        if self.is_subnet_of(other) {
+            return vec![];
+        }
+        let version = self.id.version();
+        let excluded = other.id.as_u128();
+        let mut id = self.id.as_u128();
+        let mut networks = vec![];
+        for prefix in self.mask.prefix + 1..=other.mask.prefix {
+            let half = 1u128 << (version.bits() - prefix);
+            // Keeps the half that does not hold the excluded network and moves into the other one
+            if excluded & half == 0 {
+                networks.push(Network::from_u128(id | half, prefix, version));
             } else {
-                octats[0] <= bcast_octs[0] && octats[3] > netid_octs[3] && octats[3] < bcast_octs[3]
+                networks.push(Network::from_u128(id, prefix, version));
+                id |= half;
             }
-        } else {
-            false
         }
-    }
-    /// getter for the broadcast property
+        networks.sort_by_key(|network| network.id.as_u128());
+        networks
+    }
+    /// Returns the reverse DNS zones that cover the network, a prefix that is not on an octet
+    /// (Ipv4) or a nibble (Ipv6) boundary is split into the zones of its subnets and Ipv4
+    /// prefixes longer than /24 get an RFC 2317 classless delegation zone
+    pub fn reverse_zones(&self) -> Vec<String> {
+        let version = self.id.version();
+        let prefix = self.mask.prefix;
+        let label_bits = match version {
+            IpVersion::V4 => 8,
+            IpVersion::V6 => 4,
+        };
+        if version == &IpVersion::V4 && prefix > 24 && prefix < 32 {
+            let id = self.id.as_u128();
+            return vec![format!(
+                "{}/{}.{}",
+                id & 0xff,
+                prefix,
+                IpAddress::reverse_name(id, version, 3)
+            )];
+        }
+        let zone_prefix = prefix.div_ceil(label_bits) * label_bits;
+        self.subnets(zone_prefix)
+            .unwrap()
+            .map(|net| IpAddress::reverse_name(net.id.as_u128(), version, zone_prefix / label_bits))
+            .collect()
+    }
+    /// getter for the broadcast property, Ipv6 networks have no broadcast so for them it is the
+    /// last address of the network, use `try_broadcast` to tell them apart
     pub fn broadcast(&self) -> &IpAddress {
         &self.broadcast
     }
+    /// Returns the broadcast of an Ipv4 network, `None` for an Ipv6 network
+    pub fn try_broadcast(&self) -> Option<&IpAddress> {
+        match self.id.version() {
+            IpVersion::V4 => Some(&self.broadcast),
+            IpVersion::V6 => None,
+        }
+    }
     /// getter for the netid property
     pub fn netid(&self) -> &IpAddress {
         &self.id
@@ -858,6 +1978,247 @@ impl Network {
     pub fn mask(&self) -> &Mask {
         &self.mask
     }
+    /// Returns an iterator over the subnets of the network with the giving prefix
+    pub fn subnets(&self, new_prefix: u8) -> Result<Subnets, Error> {
+        let version = self.id.version();
+        let prefix = *self.mask.prefix();
+        if new_prefix < prefix || new_prefix > version.bits() {
+            return Err(Error::new(InvalidPrefix, new_prefix));
+        }
+        Ok(Subnets {
+            next: Some(self.id.as_u128()),
+            last: self.id.as_u128() | Self::host_bits_mask(prefix, version),
+            prefix: new_prefix,
+            version: version.clone(),
+        })
+    }
+    /// Splits the network into `count` equally sized subnets, `count` is rounded up to the
+    /// next power of two so the subnets cover the whole network
+    pub fn split(&self, count: u32) -> Result<Vec<Network>, Error> {
+        if count == 0 {
+            return Err(Error::new(InvalidPrefix, count));
+        }
+        let new_prefix = count
+            .checked_next_power_of_two()
+            .map(|count| *self.mask.prefix() as u32 + count.trailing_zeros())
+            .filter(|new_prefix| *new_prefix <= self.id.version().bits() as u32)
+            .ok_or_else(|| Error::new(InsufficientAddressSpace, count))?;
+        Ok(self.subnets(new_prefix as u8)?.collect())
+    }
+    /// Allocates a subnet for each giving hosts requirement using VLSM.
+    /// The subnets are allocated from the biggest to the smallest requirement so each one is
+    /// the smallest network that fits, and are returned in the order of the requirements
+    pub fn vlsm(&self, hosts: &[u128]) -> Result<Vec<Network>, Error> {
+        let version = self.id.version();
+        let prefix = *self.mask.prefix();
+        let mut order = (0..hosts.len()).collect::<Vec<usize>>();
+        order.sort_by(|a, b| hosts[*b].cmp(&hosts[*a]));
+        let mut allocated: Vec<Option<Network>> = vec![None; hosts.len()];
+        let mut next = Some(self.id.as_u128());
+        let last = self.id.as_u128() | Self::host_bits_mask(prefix, version);
+        for i in order {
+            let insufficient = || Error::new(InsufficientAddressSpace, hosts[i]);
+            let start = next.ok_or_else(insufficient)?;
+            let new_prefix = Self::prefix_for_hosts(hosts[i], version)
+                .filter(|new_prefix| *new_prefix >= prefix)
+                .ok_or_else(insufficient)?;
+            let end = start | Self::host_bits_mask(new_prefix, version);
+            next = if end >= last { None } else { Some(end + 1) };
+            allocated[i] = Some(Network::from_u128(start, new_prefix, version));
+        }
+        Ok(allocated.into_iter().flatten().collect())
+    }
+    /// Returns a lazy iterator over the usable hosts of the network, /31 and /32 networks have
+    /// no net id and broadcast so all of their addresses are hosts
+    pub fn hosts(&self) -> Hosts {
+        let (first, last) = self.host_bounds();
+        Hosts {
+            next: Some(first),
+            last,
+            version: self.id.version().clone(),
+        }
+    }
+    /// Returns a lazy iterator over all the addresses of the network including the net id and
+    /// the broadcast
+    pub fn addresses(&self) -> Hosts {
+        let (first, last) = self.bounds();
+        Hosts {
+            next: Some(first),
+            last,
+            version: self.id.version().clone(),
+        }
+    }
+    /// Returns the number of addresses in the network
+    pub fn size(&self) -> u128 {
+        Self::host_bits_mask(*self.mask.prefix(), self.id.version()).saturating_add(1)
+    }
+    /// Returns the address at the giving index of the network, negative indexes count
+    /// backwards from the last address (-1 is the last address)
+    pub fn nth(&self, index: i128) -> Option<IpAddress> {
+        let (first, last) = self.bounds();
+        let offset = if index >= 0 {
+            index as u128
+        } else {
+            index.unsigned_abs() - 1
+        };
+        if offset > last - first {
+            return None;
+        }
+        let value = if index >= 0 {
+            first + offset
+        } else {
+            last - offset
+        };
+        IpAddress::from_u128(value, self.id.version()).ok()
+    }
+    /// Returns the first usable host of the network
+    pub fn first_host(&self) -> IpAddress {
+        IpAddress::from_u128(self.host_bounds().0, self.id.version()).unwrap()
+    }
+    /// Returns the last usable host of the network
+    pub fn last_host(&self) -> IpAddress {
+        IpAddress::from_u128(self.host_bounds().1, self.id.version()).unwrap()
+    }
+    /// Returns the integer values of the first and the last usable hosts of the network
+    fn host_bounds(&self) -> (u128, u128) {
+        let (first, last) = self.bounds();
+        if *self.id.version() == IpVersion::V4 && *self.mask.prefix() < 31 {
+            (first + 1, last - 1)
+        } else {
+            (first, last)
+        }
+    }
+    /// Collapses the giving networks into the smallest equivalent list of networks by merging
+    /// adjacent and overlapping prefixes, IPv4 networks are returned before IPv6 networks
+    pub fn summarize(networks: &[Network]) -> Vec<Network> {
+        let mut summary = vec![];
+        for version in [IpVersion::V4, IpVersion::V6] {
+            let mut ranges = networks
+                .iter()
+                .filter(|net| *net.id.version() == version)
+                .map(|net| net.bounds())
+                .collect::<Vec<(u128, u128)>>();
+            ranges.sort();
+            let mut merged: Vec<(u128, u128)> = vec![];
+            for (start, end) in ranges {
+                match merged.last_mut() {
+                    Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                    _ => merged.push((start, end)),
+                }
+            }
+            for (start, end) in merged {
+                summary.append(&mut Self::range_to_networks(start, end, &version));
+            }
+        }
+        summary
+    }
+    /// Returns the smallest single network that covers all the giving networks (summary route)
+    pub fn supernet(networks: &[Network]) -> Result<Network, Error> {
+        let version = match networks.first() {
+            Some(net) => net.id.version().clone(),
+            None => return Err(Error::new(InvalidNetwork, "")),
+        };
+        if let Some(net) = networks.iter().find(|net| *net.id.version() != version) {
+            return Err(Error::new(InvalidNetwork, net));
+        }
+        let first = networks.iter().map(|net| net.bounds().0).min().unwrap();
+        let last = networks.iter().map(|net| net.bounds().1).max().unwrap();
+        let diff_bits = 128 - (first ^ last).leading_zeros() as u8;
+        let prefix = version.bits() - diff_bits;
+        Ok(Network::from_u128(
+            first & !Self::host_bits_mask(prefix, &version),
+            prefix,
+            &version,
+        ))
+    }
+    /// Returns the integer values of the first and the last addresses of the network
+    fn bounds(&self) -> (u128, u128) {
+        let id = self.id.as_u128();
+        (
+            id,
+            id | Self::host_bits_mask(*self.mask.prefix(), self.id.version()),
+        )
+    }
+    /// Returns the smallest list of networks that covers exactly the giving range of addresses
+    fn range_to_networks(mut start: u128, end: u128, version: &IpVersion) -> Vec<Network> {
+        let bits = version.bits();
+        let mut networks = vec![];
+        loop {
+            let mut host_bits = start.trailing_zeros().min(bits as u32) as u8;
+            while start | Self::host_bits_mask(bits - host_bits, version) > end {
+                host_bits -= 1;
+            }
+            let last = start | Self::host_bits_mask(bits - host_bits, version);
+            networks.push(Network::from_u128(start, bits - host_bits, version));
+            if last >= end {
+                break;
+            }
+            start = last + 1;
+        }
+        networks
+    }
+    /// Creates a Network instance from the integer value of its net id
+    fn from_u128(id: u128, prefix: u8, version: &IpVersion) -> Network {
+        let mut broadcast =
+            IpAddress::from_u128(id | Self::host_bits_mask(prefix, version), version).unwrap();
+        if *version == IpVersion::V4 {
+            broadcast.kind = IpKind::Broadcast;
+        }
+        Network {
+            id: IpAddress::from_u128(id, version).unwrap(),
+            mask: Mask::from_version_prefix(prefix, version).unwrap(),
+            broadcast,
+        }
+    }
+    /// Returns the host part of a network with the giving prefix as a bit mask
+    fn host_bits_mask(prefix: u8, version: &IpVersion) -> u128 {
+        let host_bits = version.bits() - prefix;
+        if host_bits == 128 {
+            u128::MAX
+        } else {
+            (1u128 << host_bits) - 1
+        }
+    }
+    /// Returns the number of usable hosts in a network with the giving number of host bits
+    fn usable_hosts(host_bits: u8, version: &IpVersion) -> u128 {
+        match version {
+            IpVersion::V4 => (1u128 << host_bits).saturating_sub(2),
+            IpVersion::V6 => Self::host_bits_mask(128 - host_bits, version).saturating_add(1),
+        }
+    }
+    /// Returns the longest prefix of a network that can hold the giving number of hosts
+    fn prefix_for_hosts(hosts: u128, version: &IpVersion) -> Option<u8> {
+        (0..=version.bits())
+            .find(|host_bits| Self::usable_hosts(*host_bits, version) >= hosts)
+            .map(|host_bits| version.bits() - host_bits)
+    }
+}
+
+impl Iterator for Subnets {
+    type Item = Network;
+    fn next(&mut self) -> Option<Self::Item> {
+        let id = self.next?;
+        let end = id | Network::host_bits_mask(self.prefix, &self.version);
+        self.next = if end >= self.last {
+            None
+        } else {
+            Some(end + 1)
+        };
+        Some(Network::from_u128(id, self.prefix, &self.version))
+    }
+}
+
+impl Iterator for Hosts {
+    type Item = IpAddress;
+    fn next(&mut self) -> Option<Self::Item> {
+        let value = self.next?;
+        self.next = if value >= self.last {
+            None
+        } else {
+            Some(value + 1)
+        };
+        IpAddress::from_u128(value, &self.version).ok()
+    }
 }
 
 impl Display for Network {
@@ -867,31 +2228,393 @@ impl Display for Network {
 }
 
 impl FromStr for Network {
-    type Err = InvalidNetwork;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |source: Option<Error>| match source {
+            Some(source) => Error::new(InvalidNetwork, s).with_source(source),
+            None => Error::new(InvalidNetwork, s),
+        };
         let network_items = s.split('/').collect::<Vec<&str>>();
         if network_items.len() != 2 {
-            return Err(InvalidNetwork);
-        }
-        let prefix = network_items[1].parse::<u8>().unwrap_or(255);
-        if prefix == 255 {
-            return Err(InvalidNetwork);
-        }
-        let mask = Mask::from_prefix(prefix);
-        if let Ok(mask) = mask {
-            if IpKind::is_netid(network_items[0], &mask) {
-                let netid = IpAddress::from_str(network_items[0]).unwrap();
-                return Ok(Network {
-                    id: netid.clone(),
-                    broadcast: IpKind::get_broadcast(netid.address().as_str(), &mask).unwrap(),
-                    mask,
-                });
-            } else {
-                return Err(InvalidNetwork);
+            return Err(invalid(None));
+        }
+        let netid = IpAddress::from_str(network_items[0]).map_err(|e| invalid(Some(e)))?;
+        let prefix = network_items[1].parse::<u8>().map_err(|_| invalid(None))?;
+        let mask =
+            Mask::from_version_prefix(prefix, netid.version()).map_err(|e| invalid(Some(e)))?;
+        Network::new(netid, mask).map_err(|_| invalid(None))
+    }
+}
+
+impl From<&IpAddress> for Network {
+    fn from(address: &IpAddress) -> Self {
+        Network::from_u128(
+            address.as_u128(),
+            address.version().bits(),
+            address.version(),
+        )
+    }
+}
+
+impl IpRange {
+    /// Creates a new IpRange instance from its first and last addresses
+    pub fn new(first: IpAddress, last: IpAddress) -> Result<IpRange, Error> {
+        if first.version() != last.version() || first > last {
+            return Err(Error::new(InvalidIpRange, format!("{first}-{last}")));
+        }
+        Ok(IpRange { first, last })
+    }
+    /// Creates a new IpRange instance that covers the addresses of a giving network
+    pub fn from_network(network: &Network) -> IpRange {
+        let (first, last) = network.bounds();
+        let version = network.netid().version();
+        IpRange {
+            first: IpAddress::from_u128(first, version).unwrap(),
+            last: IpAddress::from_u128(last, version).unwrap(),
+        }
+    }
+    /// Getter for the first address of the range
+    pub fn first(&self) -> &IpAddress {
+        &self.first
+    }
+    /// Getter for the last address of the range
+    pub fn last(&self) -> &IpAddress {
+        &self.last
+    }
+    /// Checks if a giving Ip address is in the range
+    pub fn contains(&self, address: &IpAddress) -> bool {
+        address >= &self.first && address <= &self.last
+    }
+    /// Returns the number of addresses in the range
+    pub fn size(&self) -> u128 {
+        (self.last.as_u128() - self.first.as_u128()).saturating_add(1)
+    }
+    /// Returns a lazy iterator over the addresses of the range
+    pub fn addresses(&self) -> Hosts {
+        Hosts {
+            next: Some(self.first.as_u128()),
+            last: self.last.as_u128(),
+            version: self.first.version().clone(),
+        }
+    }
+    /// Returns the smallest list of networks that covers exactly the range
+    pub fn to_networks(&self) -> Vec<Network> {
+        Network::range_to_networks(
+            self.first.as_u128(),
+            self.last.as_u128(),
+            self.first.version(),
+        )
+    }
+}
+
+impl From<&Network> for IpRange {
+    fn from(network: &Network) -> Self {
+        IpRange::from_network(network)
+    }
+}
+
+impl Display for IpRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}-{}", self.first, self.last)
+    }
+}
+
+impl FromStr for IpRange {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |e| Error::new(InvalidIpRange, s).with_source(e);
+        match s.split_once('-') {
+            Some((first, last)) => IpRange::new(
+                IpAddress::from_str(first.trim()).map_err(invalid)?,
+                IpAddress::from_str(last.trim()).map_err(invalid)?,
+            ),
+            None => Err(Error::new(InvalidIpRange, s)),
+        }
+    }
+}
+
+impl<T> TrieNode<T> {
+    fn new() -> Self {
+        TrieNode {
+            value: None,
+            children: [None, None],
+        }
+    }
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.iter().all(Option::is_none)
+    }
+    /// Returns a trie that holds only the path to the giving network
+    fn path(network: &Network) -> TrieNode<T>
+    where
+        T: Default,
+    {
+        let key = network.netid().as_u128();
+        let version = network.netid().version();
+        let mut node = TrieNode::new();
+        node.value = Some(T::default());
+        for depth in (0..*network.mask().prefix()).rev() {
+            let mut parent = TrieNode::new();
+            parent.children[version.bit(key, depth)] = Some(Box::new(node));
+            node = parent;
+        }
+        node
+    }
+}
+
+impl<T: Clone> IpSet<T> {
+    /// Creates a new empty IpSet instance
+    pub fn new() -> IpSet<T> {
+        IpSet { v4: None, v6: None }
+    }
+    /// Inserts a network with a value to the set, returns the previous value of the network
+    pub fn insert(&mut self, network: &Network, value: T) -> Option<T> {
+        let key = network.netid().as_u128();
+        let version = network.netid().version();
+        let mut node = self
+            .root_mut(version)
+            .get_or_insert_with(|| Box::new(TrieNode::new()));
+        for depth in 0..*network.mask().prefix() {
+            node = node.children[version.bit(key, depth)]
+                .get_or_insert_with(|| Box::new(TrieNode::new()));
+        }
+        node.value.replace(value)
+    }
+    /// Inserts a single address with a value to the set
+    pub fn insert_address(&mut self, address: &IpAddress, value: T) -> Option<T> {
+        self.insert(&Network::from(address), value)
+    }
+    /// Removes all the addresses of a network from the set, prefixes that only partly overlap
+    /// the network are split so the rest of their addresses stay in the set
+    pub fn remove(&mut self, network: &Network) {
+        let path = TrieNode::<()>::path(network);
+        Self::subtract(self.root_mut(network.netid().version()), &path, None);
+    }
+    /// Removes a single address from the set
+    pub fn remove_address(&mut self, address: &IpAddress) {
+        self.remove(&Network::from(address))
+    }
+    /// Returns the value of the exact giving network if it is in the set
+    pub fn get(&self, network: &Network) -> Option<&T> {
+        let key = network.netid().as_u128();
+        let version = network.netid().version();
+        let mut node = self.root(version).as_deref()?;
+        for depth in 0..*network.mask().prefix() {
+            node = node.children[version.bit(key, depth)].as_deref()?;
+        }
+        node.value.as_ref()
+    }
+    /// Checks if a giving Ip address is covered by any network of the set
+    pub fn contains(&self, address: &IpAddress) -> bool {
+        self.longest_match(address).is_some()
+    }
+    /// Returns the most specific network of the set that contains the giving address
+    /// together with its value
+    pub fn longest_match(&self, address: &IpAddress) -> Option<(Network, &T)> {
+        let key = address.as_u128();
+        let version = address.version();
+        let mut node = self.root(version).as_deref()?;
+        let mut best = node.value.as_ref().map(|value| (0, value));
+        for depth in 0..version.bits() {
+            match node.children[version.bit(key, depth)].as_deref() {
+                Some(child) => node = child,
+                None => break,
             }
-        } else {
-            return Err(InvalidNetwork);
+            if let Some(value) = &node.value {
+                best = Some((depth + 1, value));
+            }
+        }
+        best.map(|(prefix, value)| {
+            let id = key & !Network::host_bits_mask(prefix, version);
+            (Network::from_u128(id, prefix, version), value)
+        })
+    }
+    /// Returns a new set with the addresses of both sets, on a shared prefix the value of self is
+    /// kept
+    pub fn union(&self, other: &IpSet<T>) -> IpSet<T> {
+        let mut set = self.clone();
+        if let Some(root) = &other.v4 {
+            Self::merge(&mut set.v4, root);
+        }
+        if let Some(root) = &other.v6 {
+            Self::merge(&mut set.v6, root);
+        }
+        set
+    }
+    /// Returns a new set with the addresses of self that are also in the other set
+    pub fn intersection<U>(&self, other: &IpSet<U>) -> IpSet<T> {
+        let mut set = self.clone();
+        match &other.v4 {
+            Some(root) => Self::intersect(&mut set.v4, root, None),
+            None => set.v4 = None,
+        }
+        match &other.v6 {
+            Some(root) => Self::intersect(&mut set.v6, root, None),
+            None => set.v6 = None,
+        }
+        set
+    }
+    /// Returns a new set with the addresses of self that are not in the other set
+    pub fn difference<U>(&self, other: &IpSet<U>) -> IpSet<T> {
+        let mut set = self.clone();
+        if let Some(root) = &other.v4 {
+            Self::subtract(&mut set.v4, root, None);
+        }
+        if let Some(root) = &other.v6 {
+            Self::subtract(&mut set.v6, root, None);
+        }
+        set
+    }
+    /// Returns the networks of the set with their values, sorted by address and then by prefix
+    pub fn entries(&self) -> Vec<(Network, &T)> {
+        let mut entries = vec![];
+        if let Some(root) = &self.v4 {
+            Self::collect(root, 0, 0, &IpVersion::V4, &mut entries);
+        }
+        if let Some(root) = &self.v6 {
+            Self::collect(root, 0, 0, &IpVersion::V6, &mut entries);
+        }
+        entries
+    }
+    /// Returns the networks of the set sorted by address and then by prefix
+    pub fn networks(&self) -> Vec<Network> {
+        self.entries()
+            .into_iter()
+            .map(|(network, _)| network)
+            .collect()
+    }
+    /// Returns the number of networks in the set
+    pub fn len(&self) -> usize {
+        [&self.v4, &self.v6]
+            .into_iter()
+            .flatten()
+            .map(|root| Self::count(root))
+            .sum()
+    }
+    /// Checks if the set is empty
+    pub fn is_empty(&self) -> bool {
+        self.v4.is_none() && self.v6.is_none()
+    }
+    fn root(&self, version: &IpVersion) -> &Option<Box<TrieNode<T>>> {
+        match version {
+            IpVersion::V4 => &self.v4,
+            IpVersion::V6 => &self.v6,
+        }
+    }
+    fn root_mut(&mut self, version: &IpVersion) -> &mut Option<Box<TrieNode<T>>> {
+        match version {
+            IpVersion::V4 => &mut self.v4,
+            IpVersion::V6 => &mut self.v6,
+        }
+    }
+    fn merge(slot: &mut Option<Box<TrieNode<T>>>, other: &TrieNode<T>) {
+        let node = slot.get_or_insert_with(|| Box::new(TrieNode::new()));
+        if node.value.is_none() {
+            node.value = other.value.clone();
+        }
+        for (child, other_child) in node.children.iter_mut().zip(&other.children) {
+            if let Some(other_child) = other_child {
+                Self::merge(child, other_child);
+            }
+        }
+    }
+    /// Keeps only the addresses of the slot that are covered by the other trie, `inherited` is
+    /// the value of the closest ancestor prefix of the slot
+    fn intersect<U>(
+        slot: &mut Option<Box<TrieNode<T>>>,
+        other: &TrieNode<U>,
+        inherited: Option<T>,
+    ) {
+        if other.value.is_some() {
+            if let Some(value) = inherited {
+                let node = slot.get_or_insert_with(|| Box::new(TrieNode::new()));
+                node.value.get_or_insert(value);
+            }
+            return;
+        }
+        if slot.is_none() && inherited.is_none() {
+            return;
+        }
+        let node = slot.get_or_insert_with(|| Box::new(TrieNode::new()));
+        let carried = node.value.take().or(inherited);
+        for (child, other_child) in node.children.iter_mut().zip(&other.children) {
+            match other_child {
+                Some(other_child) => Self::intersect(child, other_child, carried.clone()),
+                None => *child = None,
+            }
+        }
+        if node.is_empty() {
+            *slot = None;
+        }
+    }
+    /// Removes the addresses that are covered by the other trie from the slot, `inherited` is
+    /// the value of the closest ancestor prefix of the slot
+    fn subtract<U>(slot: &mut Option<Box<TrieNode<T>>>, other: &TrieNode<U>, inherited: Option<T>) {
+        if other.value.is_some() {
+            *slot = None;
+            return;
+        }
+        if slot.is_none() && inherited.is_none() {
+            return;
+        }
+        let node = slot.get_or_insert_with(|| Box::new(TrieNode::new()));
+        let carried = node.value.take().or(inherited);
+        for (child, other_child) in node.children.iter_mut().zip(&other.children) {
+            match (other_child, &carried) {
+                (Some(other_child), _) => Self::subtract(child, other_child, carried.clone()),
+                (None, Some(value)) => {
+                    child
+                        .get_or_insert_with(|| Box::new(TrieNode::new()))
+                        .value
+                        .get_or_insert_with(|| value.clone());
+                }
+                (None, None) => {}
+            }
+        }
+        if node.is_empty() {
+            *slot = None;
+        }
+    }
+    fn collect<'a>(
+        node: &'a TrieNode<T>,
+        key: u128,
+        depth: u8,
+        version: &IpVersion,
+        entries: &mut Vec<(Network, &'a T)>,
+    ) {
+        if let Some(value) = &node.value {
+            entries.push((Network::from_u128(key, depth, version), value));
+        }
+        for (bit, child) in node.children.iter().enumerate() {
+            if let Some(child) = child {
+                let key = key | ((bit as u128) << (version.bits() - 1 - depth));
+                Self::collect(child, key, depth + 1, version, entries);
+            }
+        }
+    }
+    fn count(node: &TrieNode<T>) -> usize {
+        node.value.is_some() as usize
+            + node
+                .children
+                .iter()
+                .flatten()
+                .map(|child| Self::count(child))
+                .sum::<usize>()
+    }
+}
+
+impl<T: Clone> Default for IpSet<T> {
+    fn default() -> Self {
+        IpSet::new()
+    }
+}
+
+impl FromIterator<Network> for IpSet {
+    fn from_iter<I: IntoIterator<Item = Network>>(networks: I) -> Self {
+        let mut set = IpSet::new();
+        for network in networks {
+            set.insert(&network, ());
         }
+        set
     }
 }
 
@@ -906,65 +2629,287 @@ pub struct Interface {
     ipv4: Option<IpAddress>,
     ipv6: Option<IpAddress>,
     mask: Option<Mask>,
+    addresses: Vec<InterfaceAddress>,
+    mtu: Option<u32>,
+    state: InterfaceState,
+    up: bool,
+    loopback: bool,
+    point_to_point: bool,
+    multicast: bool,
+    broadcast: bool,
+    counters: Option<InterfaceCounters>,
+}
+
+/// # InterfaceEvent
+/// `InterfaceEvent` - change of a local network interface reported by the kernel
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub enum InterfaceEvent {
+    LinkUp(Interface),
+    LinkDown(Interface),
+    LinkRemoved(Interface),
+    AddressAdded(Interface, InterfaceAddress),
+    AddressRemoved(Interface, InterfaceAddress),
+}
+
+/// # InterfaceWatcher
+/// `InterfaceWatcher` - subscription to the rtnetlink link and address notifications
+#[cfg(target_os = "linux")]
+#[derive(Debug)]
+pub struct InterfaceWatcher {
+    socket: std::os::fd::OwnedFd,
+    events: std::collections::VecDeque<InterfaceEvent>,
+    // The last known operational state (up and running) of every link by its index
+    links: HashMap<u32, bool>,
+    // The last known interfaces by their index, kept up to date from the notifications so
+    // an event doesn't have to enumerate the interfaces again
+    interfaces: HashMap<u32, Interface>,
+    buffer: Vec<u8>,
+    failed: bool,
+}
+
+/// # Route
+/// `Route` - entry of the kernel routing table
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct Route {
+    destination: Network,
+    gateway: Option<IpAddress>,
+    interface: String,
+    metric: u32,
+}
+
+/// # Neighbor
+/// `Neighbor` - entry of the neighbor (ARP/NDP) cache
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct Neighbor {
+    address: IpAddress,
+    mac: Option<MacAddress>,
+    vendor: Option<String>,
+    interface: String,
+    state: NeighborState,
+}
+
+/// # NeighborState
+/// `NeighborState` - neighbor unreachability detection (NUD) state of a neighbor cache entry
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub enum NeighborState {
+    Incomplete,
+    Reachable,
+    Stale,
+    Delay,
+    Probe,
+    Failed,
+    NoArp,
+    Permanent,
+    /// A resolved entry of `/proc/net/arp`, which does not tell its NUD state
+    Complete,
+    None,
+}
+
+/// # InterfaceAddress
+/// `InterfaceAddress` - address assigned to a network interface together with its subnet mask
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct InterfaceAddress {
+    address: IpAddress,
+    mask: Mask,
+}
+
+/// # InterfaceState
+/// `InterfaceState` - operational state of a network interface (RFC 2863)
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub enum InterfaceState {
+    Up,
+    Down,
+    Dormant,
+    LowerLayerDown,
+    NotPresent,
+    Testing,
+    Unknown,
+}
+
+/// # InterfaceCounters
+/// `InterfaceCounters` - traffic counters of a network interface
+#[derive(Debug, Clone, Default, PartialEq, FromLua)]
+pub struct InterfaceCounters {
+    rx_bytes: u64,
+    rx_packets: u64,
+    rx_errors: u64,
+    rx_dropped: u64,
+    tx_bytes: u64,
+    tx_packets: u64,
+    tx_errors: u64,
+    tx_dropped: u64,
 }
 
 impl Interface {
     /// Returns all the network interfaces on the local machine
     pub fn all() -> Vec<Interface> {
-        let mut infs = vec![];
-        for inf in interfaces() {
-            match Self::by_index(inf.index) {
-                Ok(res) => infs.push(res),
-                Err(_) => continue,
-            }
-        }
-        infs
+        interfaces()
+            .into_iter()
+            .map(Self::from_network_interface)
+            .collect()
     }
     /// Returns local network interface by index
-    pub fn by_index(index: u32) -> Result<Interface, InterfaceNotExists> {
-        let mut mac: Option<MacAddress> = None;
-        let mut ipv4: Option<IpAddress> = None;
-        let mut ipv6: Option<IpAddress> = None;
-        let mut mask: Option<Mask> = None;
+    pub fn by_index(index: u32) -> Result<Interface, Error> {
         for inf in interfaces() {
             if inf.index == index {
-                if inf.ips.len() > 0 {
-                    if let IpNetwork::V4(addr) = inf.ips[0] {
-                        ipv4 = Some(IpAddress::from(&IpAddr::V4(addr.ip())));
-                        if let Ok(submask) = Mask::from_prefix(addr.prefix()) {
-                            mask = Some(submask);
-                        }
+                return Ok(Self::from_network_interface(inf));
+            }
+        }
+        Err(Error::new(InterfaceNotExists, index))
+    }
+    /// Builds an Interface instance from a pnet interface and the `/sys/class/net` attributes
+    fn from_network_interface(inf: NetworkInterface) -> Interface {
+        let addresses = inf
+            .ips
+            .iter()
+            .filter_map(|ip| match ip {
+                IpNetwork::V4(addr) => Some(InterfaceAddress {
+                    address: IpAddress::from(&IpAddr::V4(addr.ip())),
+                    mask: Mask::from_prefix(addr.prefix()).ok()?,
+                }),
+                IpNetwork::V6(addr) => Some(InterfaceAddress {
+                    address: IpAddress::from(&IpAddr::V6(addr.ip())),
+                    mask: Mask::from_prefix_v6(addr.prefix()).ok()?,
+                }),
+            })
+            .collect::<Vec<InterfaceAddress>>();
+        let state = match Self::read_attribute(&inf.name, "operstate").as_deref() {
+            Some("up") => InterfaceState::Up,
+            Some("down") => InterfaceState::Down,
+            Some("dormant") => InterfaceState::Dormant,
+            Some("lowerlayerdown") => InterfaceState::LowerLayerDown,
+            Some("notpresent") => InterfaceState::NotPresent,
+            Some("testing") => InterfaceState::Testing,
+            _ => InterfaceState::Unknown,
+        };
+        let mut interface = Interface {
+            ipv4: None,
+            mask: None,
+            ipv6: None,
+            mtu: Self::read_attribute(&inf.name, "mtu").and_then(|mtu| mtu.parse().ok()),
+            counters: InterfaceCounters::read(&inf.name),
+            mac: inf.mac.map(|mac| MacAddress::new(mac.octets())),
+            up: inf.is_up(),
+            loopback: inf.is_loopback(),
+            point_to_point: inf.is_point_to_point(),
+            multicast: inf.is_multicast(),
+            broadcast: inf.is_broadcast(),
+            name: inf.name,
+            index: inf.index,
+            description: inf.description,
+            addresses,
+            state,
+        };
+        interface.update_primary();
+        interface
+    }
+    /// Sets the ipv4 address, its mask and the ipv6 address to the first addresses of the
+    /// interface of each version
+    fn update_primary(&mut self) {
+        let first = |version: IpVersion| {
+            self.addresses
+                .iter()
+                .find(|address| address.address.version() == &version)
+                .cloned()
+        };
+        let ipv4 = first(IpVersion::V4);
+        self.ipv6 = first(IpVersion::V6).map(|address| address.address);
+        self.mask = ipv4.as_ref().map(|address| address.mask.clone());
+        self.ipv4 = ipv4.map(|address| address.address);
+    }
+    /// Returns an interface holding only the index and the name, for a link the netlink
+    /// notifications tell nothing else about
+    #[cfg(target_os = "linux")]
+    fn not_present(index: u32, name: String) -> Interface {
+        Interface {
+            name,
+            index,
+            description: String::new(),
+            mac: None,
+            ipv4: None,
+            ipv6: None,
+            mask: None,
+            addresses: vec![],
+            mtu: None,
+            state: InterfaceState::NotPresent,
+            up: false,
+            loopback: false,
+            point_to_point: false,
+            multicast: false,
+            broadcast: false,
+            counters: None,
+        }
+    }
+    /// Updates the interface from the flags and the attributes of a netlink link message
+    #[cfg(target_os = "linux")]
+    fn update_link(&mut self, flags: u32, attributes: &[(u16, &[u8])]) {
+        let flag = |flag: i32| flags & flag as u32 != 0;
+        self.up = flag(libc::IFF_UP);
+        self.loopback = flag(libc::IFF_LOOPBACK);
+        self.point_to_point = flag(libc::IFF_POINTOPOINT);
+        self.multicast = flag(libc::IFF_MULTICAST);
+        self.broadcast = flag(libc::IFF_BROADCAST);
+        for (kind, value) in attributes {
+            match *kind {
+                libc::IFLA_IFNAME => self.name = netlink::string(value),
+                libc::IFLA_MTU if value.len() >= 4 => self.mtu = Some(netlink::u32_at(value, 0)),
+                libc::IFLA_ADDRESS => {
+                    if let Ok(bytes) = <[u8; 6]>::try_from(*value) {
+                        self.mac = Some(MacAddress::new(bytes));
                     }
                 }
-                if inf.ips.len() > 1 {
-                    if let IpNetwork::V6(addr) = inf.ips[1] {
-                        ipv6 = Some(IpAddress::from(&IpAddr::V6(addr.ip())))
+                libc::IFLA_OPERSTATE if !value.is_empty() => {
+                    self.state = match value[0] as i32 {
+                        libc::IF_OPER_UP => InterfaceState::Up,
+                        libc::IF_OPER_DOWN => InterfaceState::Down,
+                        libc::IF_OPER_DORMANT => InterfaceState::Dormant,
+                        libc::IF_OPER_LOWERLAYERDOWN => InterfaceState::LowerLayerDown,
+                        libc::IF_OPER_NOTPRESENT => InterfaceState::NotPresent,
+                        libc::IF_OPER_TESTING => InterfaceState::Testing,
+                        _ => InterfaceState::Unknown,
                     }
                 }
-                if let Some(mac_addr) = inf.mac {
-                    mac = Some(MacAddress::new(mac_addr.octets()));
+                libc::IFLA_STATS64 if value.len() >= 64 => {
+                    // rtnl_link_stats64 starts with the rx/tx packets, bytes, errors and drops
+                    let counter = |at: usize| netlink::u64_at(value, at * 8);
+                    self.counters = Some(InterfaceCounters {
+                        rx_packets: counter(0),
+                        tx_packets: counter(1),
+                        rx_bytes: counter(2),
+                        tx_bytes: counter(3),
+                        rx_errors: counter(4),
+                        tx_errors: counter(5),
+                        rx_dropped: counter(6),
+                        tx_dropped: counter(7),
+                    });
                 }
-                return Ok(Interface {
-                    name: inf.name.to_string(),
-                    index,
-                    description: inf.description,
-                    mac,
-                    ipv4,
-                    ipv6,
-                    mask,
-                });
+                _ => {}
             }
         }
-        Err(InterfaceNotExists)
+    }
+    /// Adds or removes an address of the interface
+    #[cfg(target_os = "linux")]
+    fn update_address(&mut self, address: &InterfaceAddress, added: bool) {
+        self.addresses.retain(|known| known != address);
+        if added {
+            self.addresses.push(address.clone());
+        }
+        self.update_primary();
+    }
+    /// Reads an attribute of an interface from `/sys/class/net`
+    fn read_attribute(name: &str, attribute: &str) -> Option<String> {
+        std::fs::read_to_string(format!("/sys/class/net/{}/{}", name, attribute))
+            .ok()
+            .map(|value| value.trim().to_string())
     }
     /// Returns local network interface by name
-    pub fn by_name(name: &str) -> Result<Interface, InterfaceNotExists> {
+    pub fn by_name(name: &str) -> Result<Interface, Error> {
         for inf in interfaces() {
             if inf.name == name {
                 return Self::by_index(inf.index);
             }
         }
-        Err(InterfaceNotExists)
+        Err(Error::new(InterfaceNotExists, name))
     }
     /// Get interface name attribute
     pub fn name(&self) -> &String {
@@ -994,14 +2939,644 @@ impl Interface {
     pub fn mask(&self) -> &Option<Mask> {
         &self.mask
     }
+    /// Get all the ipv4 and ipv6 addresses of the interface
+    pub fn addresses(&self) -> &Vec<InterfaceAddress> {
+        &self.addresses
+    }
+    /// Get the MTU attribute
+    pub fn mtu(&self) -> &Option<u32> {
+        &self.mtu
+    }
+    /// Get the operational state attribute
+    pub fn state(&self) -> &InterfaceState {
+        &self.state
+    }
+    /// Checks if the interface is administratively up
+    pub fn is_up(&self) -> bool {
+        self.up
+    }
+    pub fn is_loopback(&self) -> bool {
+        self.loopback
+    }
+    pub fn is_point_to_point(&self) -> bool {
+        self.point_to_point
+    }
+    pub fn is_multicast(&self) -> bool {
+        self.multicast
+    }
+    pub fn is_broadcast(&self) -> bool {
+        self.broadcast
+    }
+    /// Get the traffic counters attribute, `None` when `/sys/class/net` is not available
+    pub fn counters(&self) -> &Option<InterfaceCounters> {
+        &self.counters
+    }
     /// Convert Interface instance to NetworkInterface instance
-    pub fn into(&self) -> Result<NetworkInterface, InterfaceConvertionFailed> {
+    pub fn into(&self) -> Result<NetworkInterface, Error> {
         for inf in interfaces() {
             if self.name == inf.name {
                 return Ok(inf);
             }
         }
-        Err(InterfaceConvertionFailed)
+        Err(Error::new(InterfaceConversionFailed, &self.name))
+    }
+}
+
+impl InterfaceAddress {
+    /// Creates a new InterfaceAddress instance
+    pub fn new(address: IpAddress, mask: Mask) -> Result<InterfaceAddress, Error> {
+        if address.version() != mask.version() {
+            return Err(Error::new(
+                InvalidMask,
+                format!("{address}/{}", mask.prefix()),
+            ));
+        }
+        Ok(InterfaceAddress { address, mask })
+    }
+    /// Checks if a giving string is a valid interface address
+    pub fn is_valid(address: &str) -> bool {
+        InterfaceAddress::from_str(address).is_ok()
+    }
+    pub fn address(&self) -> &IpAddress {
+        &self.address
+    }
+    pub fn mask(&self) -> &Mask {
+        &self.mask
+    }
+    /// Returns the network the address belongs to
+    pub fn network(&self) -> Network {
+        Network::from_u128(
+            self.address.as_u128() & self.mask.as_u128(),
+            *self.mask.prefix(),
+            self.address.version(),
+        )
+    }
+}
+
+impl InterfaceEvent {
+    /// Returns the interface the event is about
+    pub fn interface(&self) -> &Interface {
+        match self {
+            InterfaceEvent::LinkUp(interface)
+            | InterfaceEvent::LinkDown(interface)
+            | InterfaceEvent::LinkRemoved(interface)
+            | InterfaceEvent::AddressAdded(interface, _)
+            | InterfaceEvent::AddressRemoved(interface, _) => interface,
+        }
+    }
+    /// Returns the added or removed address of an address event
+    pub fn address(&self) -> Option<&InterfaceAddress> {
+        match self {
+            InterfaceEvent::AddressAdded(_, address)
+            | InterfaceEvent::AddressRemoved(_, address) => Some(address),
+            _ => None,
+        }
+    }
+    /// Returns the kind of the event, `link_up`, `link_down`, `link_removed`, `address_added` or
+    /// `address_removed`
+    pub fn kind(&self) -> &str {
+        match self {
+            InterfaceEvent::LinkUp(_) => "link_up",
+            InterfaceEvent::LinkDown(_) => "link_down",
+            InterfaceEvent::LinkRemoved(_) => "link_removed",
+            InterfaceEvent::AddressAdded(_, _) => "address_added",
+            InterfaceEvent::AddressRemoved(_, _) => "address_removed",
+        }
+    }
+}
+
+impl Display for InterfaceEvent {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.address() {
+            Some(address) => write!(f, "{} {} {}", self.kind(), self.interface().name, address),
+            None => write!(f, "{} {}", self.kind(), self.interface().name),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl InterfaceWatcher {
+    /// Subscribes to the link and ipv4/ipv6 address notifications of the network namespace of the
+    /// calling thread
+    pub fn new() -> Result<InterfaceWatcher, Error> {
+        let groups = libc::RTMGRP_LINK | libc::RTMGRP_IPV4_IFADDR | libc::RTMGRP_IPV6_IFADDR;
+        let socket = netlink::socket(groups as u32).ok_or_else(Self::failure)?;
+        let (links, interfaces) = Self::snapshot();
+        Ok(InterfaceWatcher {
+            socket,
+            events: std::collections::VecDeque::new(),
+            links,
+            interfaces,
+            buffer: vec![0u8; 8192],
+            failed: false,
+        })
+    }
+    /// Waits for the next event, `None` is returned if no event arrived before the timeout.
+    /// An `InterfaceEventsLost` error is returned when the kernel dropped notifications because
+    /// they were not read fast enough, the watcher can still be used after it
+    pub fn next_event(
+        &mut self,
+        timeout: Option<std::time::Duration>,
+    ) -> Result<Option<InterfaceEvent>, Error> {
+        use std::os::fd::AsRawFd;
+        let deadline = timeout.map(|timeout| std::time::Instant::now() + timeout);
+        while self.events.is_empty() {
+            let wait = match deadline {
+                Some(deadline) => {
+                    let left = deadline.saturating_duration_since(std::time::Instant::now());
+                    left.as_millis().min(i32::MAX as u128) as i32
+                }
+                None => -1,
+            };
+            let mut poll = libc::pollfd {
+                fd: self.socket.as_raw_fd(),
+                events: libc::POLLIN,
+                revents: 0,
+            };
+            // SAFETY: poll gets a valid descriptor and a pollfd that outlives the call
+            match unsafe { libc::poll(&mut poll, 1, wait) } {
+                0 => return Ok(None),
+                ready if ready < 0 => {
+                    if std::io::Error::last_os_error().kind() == std::io::ErrorKind::Interrupted {
+                        continue;
+                    }
+                    return Err(Self::failure());
+                }
+                _ => {}
+            }
+            let read = match netlink::receive(&self.socket, &mut self.buffer) {
+                Ok(read) => read,
+                Err(e) if e.raw_os_error() == Some(libc::ENOBUFS) => {
+                    // The states of the dropped link notifications are unknown, start over
+                    (self.links, self.interfaces) = Self::snapshot();
+                    return Err(Error::new(InterfaceEventsLost, "").with_source(e));
+                }
+                Err(e) => return Err(Error::new(InterfaceWatchFailed, "").with_source(e)),
+            };
+            let buffer = std::mem::take(&mut self.buffer);
+            for (kind, _, message) in netlink::messages(&buffer[..read]) {
+                if let Some(event) = self.parse_message(kind, message) {
+                    self.events.push_back(event);
+                }
+            }
+            self.buffer = buffer;
+        }
+        Ok(self.events.pop_front())
+    }
+    /// Returns the error of a failed netlink call, with the OS error as its source
+    fn failure() -> Error {
+        Error::new(InterfaceWatchFailed, "").with_source(std::io::Error::last_os_error())
+    }
+    /// Returns the current operational state of every link and every interface by its index
+    fn snapshot() -> (HashMap<u32, bool>, HashMap<u32, Interface>) {
+        let mut links = HashMap::new();
+        let mut known = HashMap::new();
+        for inf in interfaces() {
+            links.insert(inf.index, inf.is_up() && inf.is_running());
+            known.insert(inf.index, Interface::from_network_interface(inf));
+        }
+        (links, known)
+    }
+    /// Parses a link or address netlink message into an event, a link message is an event only
+    /// when it changes the operational state of the link (and not its mtu, name or other flags)
+    fn parse_message(&mut self, kind: u16, message: &[u8]) -> Option<InterfaceEvent> {
+        match kind {
+            libc::RTM_NEWLINK | libc::RTM_DELLINK if message.len() >= 16 => {
+                let index = netlink::u32_at(message, 4);
+                let flags = netlink::u32_at(message, 8);
+                let running = libc::IFF_UP as u32 | libc::IFF_RUNNING as u32;
+                let up = flags & running == running;
+                let interface = self
+                    .interfaces
+                    .entry(index)
+                    .or_insert_with(|| Interface::not_present(index, String::new()));
+                interface.update_link(flags, &netlink::attributes(&message[16..]));
+                if kind == libc::RTM_DELLINK {
+                    self.links.remove(&index);
+                    let mut interface = self.interfaces.remove(&index)?;
+                    interface.state = InterfaceState::NotPresent;
+                    return Some(InterfaceEvent::LinkRemoved(interface));
+                }
+                let interface = interface.clone();
+                if self.links.insert(index, up) == Some(up) {
+                    return None;
+                }
+                Some(if up {
+                    InterfaceEvent::LinkUp(interface)
+                } else {
+                    InterfaceEvent::LinkDown(interface)
+                })
+            }
+            libc::RTM_NEWADDR | libc::RTM_DELADDR if message.len() >= 8 => {
+                let (family, prefix) = (message[0] as i32, message[1]);
+                let index = netlink::u32_at(message, 4);
+                let attributes = netlink::attributes(&message[8..]);
+                let attribute = |kind: u16| {
+                    attributes
+                        .iter()
+                        .find(|(attribute, _)| *attribute == kind)
+                        .map(|(_, value)| *value)
+                };
+                // The local address is the address of the interface on point-to-point links
+                let address = attribute(libc::IFA_LOCAL).or(attribute(libc::IFA_ADDRESS))?;
+                let address = InterfaceAddress {
+                    address: netlink::ip_address(family, address)?,
+                    mask: match family {
+                        libc::AF_INET => Mask::from_prefix(prefix).ok()?,
+                        _ => Mask::from_prefix_v6(prefix).ok()?,
+                    },
+                };
+                let name = attribute(libc::IFA_LABEL)
+                    .map(netlink::string)
+                    .unwrap_or_default();
+                // The address of a link that is already removed goes on an interface of its own
+                let mut removed = Interface::not_present(index, name);
+                let interface = self.interfaces.get_mut(&index).unwrap_or(&mut removed);
+                interface.update_address(&address, kind == libc::RTM_NEWADDR);
+                let interface = interface.clone();
+                Some(if kind == libc::RTM_NEWADDR {
+                    InterfaceEvent::AddressAdded(interface, address)
+                } else {
+                    InterfaceEvent::AddressRemoved(interface, address)
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Iterator for InterfaceWatcher {
+    type Item = Result<InterfaceEvent, Error>;
+    /// Blocks until the next event, lost events are reported as an `InterfaceEventsLost` error
+    /// and the iteration goes on, any other error ends the iteration after it is reported
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        match self.next_event(None) {
+            Ok(event) => event.map(Ok),
+            Err(e) => {
+                self.failed = e.kind() != InterfaceEventsLost;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+impl Route {
+    /// The route flags of the kernel (`RTF_UP`, `RTF_GATEWAY` and `RTF_REJECT`)
+    const UP: u32 = 0x0001;
+    const GATEWAY: u32 = 0x0002;
+    const REJECT: u32 = 0x0200;
+    /// Returns the ipv4 and ipv6 routes of the kernel, read from `/proc/net/route` (main table)
+    /// and `/proc/net/ipv6_route` (every table)
+    pub fn all() -> Vec<Route> {
+        let read = |path: &str| std::fs::read_to_string(path).unwrap_or_default();
+        let mut routes = Self::parse_ipv4_table(&read("/proc/net/route"));
+        routes.extend(Self::parse_ipv6_table(&read("/proc/net/ipv6_route")));
+        routes
+    }
+    /// Parses the routes of the `/proc/net/route` format, the routes that are not up are left out
+    pub fn parse_ipv4_table(data: &str) -> Vec<Route> {
+        // The addresses are printed as the hex value of the network ordered bytes
+        let address = |hex: &str| u32::from_str_radix(hex, 16).ok().map(u32::to_ne_bytes);
+        data.lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<&str>>();
+                let flags = u32::from_str_radix(fields.get(3)?, 16).ok()?;
+                if flags & Self::UP == 0 || flags & Self::REJECT != 0 {
+                    return None;
+                }
+                let destination = u32::from_be_bytes(address(fields[1])?);
+                let mask = u32::from_be_bytes(address(fields.get(7)?)?);
+                if mask.leading_ones() != mask.count_ones() {
+                    return None;
+                }
+                let gateway = address(fields[2])?;
+                Some(Route {
+                    destination: Network::from_u128(
+                        (destination & mask) as u128,
+                        mask.count_ones() as u8,
+                        &IpVersion::V4,
+                    ),
+                    gateway: match flags & Self::GATEWAY {
+                        0 => None,
+                        _ => Some(IpAddress::from(&IpAddr::V4(Ipv4Addr::from(gateway)))),
+                    },
+                    interface: fields[0].to_string(),
+                    metric: fields.get(6)?.parse().ok()?,
+                })
+            })
+            .collect()
+    }
+    /// Parses the routes of the `/proc/net/ipv6_route` format, the routes that are not up are
+    /// left out
+    pub fn parse_ipv6_table(data: &str) -> Vec<Route> {
+        let address = |hex: &str| u128::from_str_radix(hex, 16).ok();
+        data.lines()
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<&str>>();
+                if fields.len() < 10 {
+                    return None;
+                }
+                let flags = u32::from_str_radix(fields[8], 16).ok()?;
+                if flags & Self::UP == 0 || flags & Self::REJECT != 0 {
+                    return None;
+                }
+                let prefix = u8::from_str_radix(fields[1], 16)
+                    .ok()
+                    .filter(|prefix| *prefix <= 128)?;
+                let destination =
+                    address(fields[0])? & !Network::host_bits_mask(prefix, &IpVersion::V6);
+                let gateway = address(fields[4])?;
+                Some(Route {
+                    destination: Network::from_u128(destination, prefix, &IpVersion::V6),
+                    gateway: match gateway {
+                        0 => None,
+                        gateway => Some(IpAddress::from(&IpAddr::V6(Ipv6Addr::from(gateway)))),
+                    },
+                    interface: fields[9].to_string(),
+                    metric: u32::from_str_radix(fields[5], 16).ok()?,
+                })
+            })
+            .collect()
+    }
+    /// Returns the route a giving address is reached through from a list of routes, the route
+    /// with the longest matching prefix and then the lowest metric
+    pub fn lookup<'a>(routes: &'a [Route], address: &IpAddress) -> Option<&'a Route> {
+        routes
+            .iter()
+            .filter(|route| route.destination.contains(address))
+            .min_by_key(|route| {
+                (
+                    std::cmp::Reverse(*route.destination.mask().prefix()),
+                    route.metric,
+                )
+            })
+    }
+    /// Returns the route of the kernel routing table a giving address is reached through
+    pub fn route_for(address: &IpAddress) -> Option<Route> {
+        Self::lookup(&Self::all(), address).cloned()
+    }
+    pub fn destination(&self) -> &Network {
+        &self.destination
+    }
+    /// Get the next hop, `None` when the destination is directly connected
+    pub fn gateway(&self) -> &Option<IpAddress> {
+        &self.gateway
+    }
+    /// Get the name of the egress interface
+    pub fn interface_name(&self) -> &String {
+        &self.interface
+    }
+    /// Returns the egress interface
+    pub fn interface(&self) -> Result<Interface, Error> {
+        Interface::by_name(&self.interface)
+    }
+    pub fn metric(&self) -> u32 {
+        self.metric
+    }
+    /// Checks if the route is a default route
+    pub fn is_default(&self) -> bool {
+        *self.destination.mask().prefix() == 0
+    }
+}
+
+impl Neighbor {
+    /// Creates a new Neighbor instance, the vendor of the mac address is resolved right away
+    pub fn new(
+        address: IpAddress,
+        mac: Option<MacAddress>,
+        interface: &str,
+        state: NeighborState,
+    ) -> Neighbor {
+        Neighbor {
+            vendor: mac.as_ref().map(|mac| mac.vendor()),
+            address,
+            mac,
+            interface: interface.to_string(),
+            state,
+        }
+    }
+    /// Returns the ipv4 and ipv6 neighbors of the local machine, read through rtnetlink or from
+    /// `/proc/net/arp` (ipv4 only) when netlink is not available
+    pub fn all() -> Vec<Neighbor> {
+        #[cfg(target_os = "linux")]
+        if let Some(neighbors) = Self::dump() {
+            return neighbors;
+        }
+        Self::parse_arp_table(&std::fs::read_to_string("/proc/net/arp").unwrap_or_default())
+    }
+    /// Parses the neighbors of the `/proc/net/arp` format, the resolved entries are reported as
+    /// complete since the file does not tell the reachable and stale ones apart
+    pub fn parse_arp_table(data: &str) -> Vec<Neighbor> {
+        data.lines()
+            .skip(1)
+            .filter_map(|line| {
+                let fields = line.split_whitespace().collect::<Vec<&str>>();
+                let address = IpAddress::from_str(fields.first()?).ok()?;
+                let flags =
+                    u16::from_str_radix(fields.get(2)?.trim_start_matches("0x"), 16).ok()?;
+                let state = if flags & 0x4 != 0 {
+                    NeighborState::Permanent
+                } else if flags & 0x2 != 0 {
+                    NeighborState::Complete
+                } else {
+                    NeighborState::Incomplete
+                };
+                let mac = MacAddress::from_str(fields.get(3)?)
+                    .ok()
+                    .filter(|mac| mac.as_bytes() != &[0; 6]);
+                Some(Neighbor::new(address, mac, fields.get(5)?, state))
+            })
+            .collect()
+    }
+    /// Reads the neighbor tables through a rtnetlink dump
+    #[cfg(target_os = "linux")]
+    fn dump() -> Option<Vec<Neighbor>> {
+        // An ndmsg of an unspecified family asks for both the ipv4 and ipv6 tables
+        let messages = netlink::dump(libc::RTM_GETNEIGH, &[0; 12])?;
+        let names = interfaces()
+            .into_iter()
+            .map(|inf| (inf.index, inf.name))
+            .collect::<HashMap<u32, String>>();
+        Some(
+            messages
+                .iter()
+                .filter(|(kind, message)| *kind == libc::RTM_NEWNEIGH && message.len() >= 12)
+                .filter_map(|(_, message)| {
+                    let family = message[0] as i32;
+                    let index = netlink::u32_at(message, 4);
+                    let state = NeighborState::from(netlink::u16_at(message, 8));
+                    let attributes = netlink::attributes(&message[12..]);
+                    let attribute = |kind: u16| {
+                        attributes
+                            .iter()
+                            .find(|(attribute, _)| *attribute == kind)
+                            .map(|(_, value)| *value)
+                    };
+                    let address = netlink::ip_address(family, attribute(libc::NDA_DST)?)?;
+                    let mac = attribute(libc::NDA_LLADDR)
+                        .and_then(|mac| <[u8; 6]>::try_from(mac).ok())
+                        .map(MacAddress::new);
+                    let interface = names.get(&index).cloned().unwrap_or(index.to_string());
+                    Some(Neighbor::new(address, mac, &interface, state))
+                })
+                .collect(),
+        )
+    }
+    pub fn address(&self) -> &IpAddress {
+        &self.address
+    }
+    /// Get the link layer address, `None` when it is not resolved yet
+    pub fn mac(&self) -> &Option<MacAddress> {
+        &self.mac
+    }
+    /// Get the vendor of the link layer address
+    pub fn vendor(&self) -> &Option<String> {
+        &self.vendor
+    }
+    pub fn interface_name(&self) -> &String {
+        &self.interface
+    }
+    /// Returns the interface the neighbor is reached through
+    pub fn interface(&self) -> Result<Interface, Error> {
+        Interface::by_name(&self.interface)
+    }
+    pub fn state(&self) -> &NeighborState {
+        &self.state
+    }
+}
+
+impl Display for Neighbor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} dev {}", self.address, self.interface)?;
+        if let Some(mac) = &self.mac {
+            write!(f, " lladdr {}", mac)?;
+        }
+        write!(f, " {}", self.state)
+    }
+}
+
+impl From<u16> for NeighborState {
+    /// Converts the NUD state bits of the kernel
+    fn from(state: u16) -> NeighborState {
+        match state {
+            0x01 => NeighborState::Incomplete,
+            0x02 => NeighborState::Reachable,
+            0x04 => NeighborState::Stale,
+            0x08 => NeighborState::Delay,
+            0x10 => NeighborState::Probe,
+            0x20 => NeighborState::Failed,
+            0x40 => NeighborState::NoArp,
+            0x80 => NeighborState::Permanent,
+            _ => NeighborState::None,
+        }
+    }
+}
+
+impl Display for NeighborState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            NeighborState::Incomplete => write!(f, "INCOMPLETE"),
+            NeighborState::Reachable => write!(f, "REACHABLE"),
+            NeighborState::Stale => write!(f, "STALE"),
+            NeighborState::Delay => write!(f, "DELAY"),
+            NeighborState::Probe => write!(f, "PROBE"),
+            NeighborState::Failed => write!(f, "FAILED"),
+            NeighborState::NoArp => write!(f, "NOARP"),
+            NeighborState::Permanent => write!(f, "PERMANENT"),
+            NeighborState::Complete => write!(f, "COMPLETE"),
+            NeighborState::None => write!(f, "NONE"),
+        }
+    }
+}
+
+impl Display for Route {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.destination)?;
+        if let Some(gateway) = &self.gateway {
+            write!(f, " via {}", gateway)?;
+        }
+        write!(f, " dev {} metric {}", self.interface, self.metric)
+    }
+}
+
+impl Display for InterfaceAddress {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.mask.prefix())
+    }
+}
+
+impl FromStr for InterfaceAddress {
+    type Err = Error;
+    /// Parses an address and its mask in any of the formats `Network::parse` supports, keeping
+    /// the host bits of the address, like `10.0.0.5/24` or `10.0.0.5 255.255.255.0`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (address, mask) = Network::parse_address_mask(s)
+            .map_err(|e| Error::new(InvalidInterfaceAddress, s).with_source(e))?;
+        InterfaceAddress::new(address, mask)
+    }
+}
+
+impl Display for InterfaceState {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            InterfaceState::Up => write!(f, "up"),
+            InterfaceState::Down => write!(f, "down"),
+            InterfaceState::Dormant => write!(f, "dormant"),
+            InterfaceState::LowerLayerDown => write!(f, "lower layer down"),
+            InterfaceState::NotPresent => write!(f, "not present"),
+            InterfaceState::Testing => write!(f, "testing"),
+            InterfaceState::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl InterfaceCounters {
+    /// Reads the counters of an interface from `/sys/class/net/<name>/statistics`
+    pub fn read(name: &str) -> Option<InterfaceCounters> {
+        let counter = |counter: &str| {
+            Interface::read_attribute(name, &format!("statistics/{}", counter))?
+                .parse::<u64>()
+                .ok()
+        };
+        Some(InterfaceCounters {
+            rx_bytes: counter("rx_bytes")?,
+            rx_packets: counter("rx_packets")?,
+            rx_errors: counter("rx_errors")?,
+            rx_dropped: counter("rx_dropped")?,
+            tx_bytes: counter("tx_bytes")?,
+            tx_packets: counter("tx_packets")?,
+            tx_errors: counter("tx_errors")?,
+            tx_dropped: counter("tx_dropped")?,
+        })
+    }
+    pub fn rx_bytes(&self) -> u64 {
+        self.rx_bytes
+    }
+    pub fn rx_packets(&self) -> u64 {
+        self.rx_packets
+    }
+    pub fn rx_errors(&self) -> u64 {
+        self.rx_errors
+    }
+    pub fn rx_dropped(&self) -> u64 {
+        self.rx_dropped
+    }
+    pub fn tx_bytes(&self) -> u64 {
+        self.tx_bytes
+    }
+    pub fn tx_packets(&self) -> u64 {
+        self.tx_packets
+    }
+    pub fn tx_errors(&self) -> u64 {
+        self.tx_errors
+    }
+    pub fn tx_dropped(&self) -> u64 {
+        self.tx_dropped
     }
 }
 
@@ -1024,6 +3599,16 @@ impl Display for Interface {
             mask = addr.mask()
         }
 
+        let mtu = match self.mtu {
+            Some(mtu) => mtu.to_string(),
+            None => "None".to_string(),
+        };
+        let addresses = self
+            .addresses
+            .iter()
+            .map(|address| address.to_string())
+            .collect::<Vec<String>>()
+            .join(" ");
         write!(
             f,
             "==== {} ====
@@ -1032,14 +3617,26 @@ description: {}
 mac:  {}
 ipv4: {}                
 ipv6: {}
-mask: {}",
-            self.name, self.index, self.description, mac, ipv4, ipv6, mask
+mask: {}
+addresses: {}
+mtu: {}
+state: {}",
+            self.name,
+            self.index,
+            self.description,
+            mac,
+            ipv4,
+            ipv6,
+            mask,
+            addresses,
+            mtu,
+            self.state
         )
     }
 }
 
 impl FromStr for Interface {
-    type Err = InterfaceNotExists;
+    type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if let Ok(index) = s.parse::<u32>() {
             Self::by_index(index)
@@ -1082,3 +3679,208 @@ impl FromStr for Url {
         }
     }
 }
+
+#[cfg(feature = "serde")]
+serde_from_str!(
+    MacAddress,
+    IpAddress,
+    Mask,
+    WildcardMask,
+    Network,
+    IpRange,
+    InterfaceAddress,
+    Path,
+    Url
+);
+
+/// An interface is represented by its name and looked up again on deserialization
+#[cfg(feature = "serde")]
+impl serde::Serialize for Interface {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.name)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Interface {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        Interface::by_name(&name).map_err(serde::de::Error::custom)
+    }
+}
+
+/// A set is represented by the list of its networks
+#[cfg(feature = "serde")]
+impl serde::Serialize for IpSet {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.networks())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IpSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<Network>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Helpers for the rtnetlink sockets of the interface watcher and the neighbor table
+#[cfg(target_os = "linux")]
+mod netlink {
+    use super::{IpAddr, IpAddress, Ipv4Addr, Ipv6Addr};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Opens a route netlink socket subscribed to the giving multicast groups
+    pub(super) fn socket(groups: u32) -> Option<OwnedFd> {
+        // SAFETY: the descriptor is owned by the OwnedFd right after it is created and the
+        // address passed to bind is a fully initialized sockaddr_nl
+        unsafe {
+            let fd = libc::socket(
+                libc::AF_NETLINK,
+                libc::SOCK_RAW | libc::SOCK_CLOEXEC,
+                libc::NETLINK_ROUTE,
+            );
+            if fd < 0 {
+                return None;
+            }
+            let socket = OwnedFd::from_raw_fd(fd);
+            let mut address: libc::sockaddr_nl = std::mem::zeroed();
+            address.nl_family = libc::AF_NETLINK as libc::sa_family_t;
+            address.nl_groups = groups;
+            if libc::bind(
+                fd,
+                &address as *const libc::sockaddr_nl as *const libc::sockaddr,
+                std::mem::size_of::<libc::sockaddr_nl>() as libc::socklen_t,
+            ) < 0
+            {
+                return None;
+            }
+            Some(socket)
+        }
+    }
+    /// Reads a datagram from the socket into the buffer, the buffer is grown to the size of the
+    /// datagram first so it is never truncated, returns the datagram length
+    pub(super) fn receive(socket: &OwnedFd, buffer: &mut Vec<u8>) -> std::io::Result<usize> {
+        let receive = |buffer: &mut [u8], flags: i32| {
+            // SAFETY: recv writes at most `buffer.len()` bytes into the buffer
+            let read = unsafe {
+                libc::recv(
+                    socket.as_raw_fd(),
+                    buffer.as_mut_ptr() as *mut libc::c_void,
+                    buffer.len(),
+                    flags,
+                )
+            };
+            usize::try_from(read).map_err(|_| std::io::Error::last_os_error())
+        };
+        // With MSG_TRUNC the real length of the datagram is returned even when it doesn't fit
+        let length = receive(buffer, libc::MSG_PEEK | libc::MSG_TRUNC)?;
+        if length > buffer.len() {
+            buffer.resize(length, 0);
+        }
+        receive(buffer, 0)
+    }
+    /// Sends a dump request with the giving family header and returns the payloads of the
+    /// answered messages as (type, payload) pairs, `None` if the kernel answered with an error
+    pub(super) fn dump(kind: u16, header: &[u8]) -> Option<Vec<(u16, Vec<u8>)>> {
+        static SEQUENCE: AtomicU32 = AtomicU32::new(1);
+        let sequence = SEQUENCE.fetch_add(1, Ordering::Relaxed);
+        let socket = socket(0)?;
+        let length = 16 + header.len() as u32;
+        let mut request = length.to_ne_bytes().to_vec();
+        request.extend_from_slice(&kind.to_ne_bytes());
+        request.extend_from_slice(&((libc::NLM_F_REQUEST | libc::NLM_F_DUMP) as u16).to_ne_bytes());
+        request.extend_from_slice(&sequence.to_ne_bytes());
+        request.extend_from_slice(&0u32.to_ne_bytes());
+        request.extend_from_slice(header);
+        // SAFETY: send reads `request.len()` bytes from the request buffer
+        let sent = unsafe {
+            libc::send(
+                socket.as_raw_fd(),
+                request.as_ptr() as *const libc::c_void,
+                request.len(),
+                0,
+            )
+        };
+        if sent < 0 {
+            return None;
+        }
+        let mut buffer = vec![0u8; 8192];
+        let mut answers = vec![];
+        loop {
+            let read = receive(&socket, &mut buffer).ok()?;
+            // Messages that don't answer this request are ignored
+            for (kind, _, message) in messages(&buffer[..read])
+                .into_iter()
+                .filter(|(_, answer, _)| *answer == sequence)
+            {
+                match kind as i32 {
+                    libc::NLMSG_DONE => return Some(answers),
+                    // An nlmsgerr with a zero error code is an acknowledgement
+                    libc::NLMSG_ERROR if message.len() >= 4 && u32_at(message, 0) == 0 => {}
+                    libc::NLMSG_ERROR => return None,
+                    _ => answers.push((kind, message.to_vec())),
+                }
+            }
+        }
+    }
+    /// Splits a datagram into its messages, as (type, sequence number, payload) triples
+    pub(super) fn messages(mut data: &[u8]) -> Vec<(u16, u32, &[u8])> {
+        let mut messages = vec![];
+        while data.len() >= 16 {
+            let length = u32_at(data, 0) as usize;
+            if length < 16 || length > data.len() {
+                break;
+            }
+            messages.push((u16_at(data, 4), u32_at(data, 8), &data[16..length]));
+            data = &data[((length + 3) & !3).min(data.len())..];
+        }
+        messages
+    }
+    /// Splits the attributes part of a message, as (type, payload) pairs
+    pub(super) fn attributes(mut data: &[u8]) -> Vec<(u16, &[u8])> {
+        let mut attributes = vec![];
+        while data.len() >= 4 {
+            let length = u16_at(data, 0) as usize;
+            if length < 4 || length > data.len() {
+                break;
+            }
+            attributes.push((
+                u16_at(data, 2) & !(libc::NLA_F_NESTED as u16),
+                &data[4..length],
+            ));
+            data = &data[((length + 3) & !3).min(data.len())..];
+        }
+        attributes
+    }
+    pub(super) fn u16_at(data: &[u8], at: usize) -> u16 {
+        u16::from_ne_bytes([data[at], data[at + 1]])
+    }
+    pub(super) fn u32_at(data: &[u8], at: usize) -> u32 {
+        u32::from_ne_bytes(data[at..at + 4].try_into().unwrap())
+    }
+    pub(super) fn u64_at(data: &[u8], at: usize) -> u64 {
+        u64::from_ne_bytes(data[at..at + 8].try_into().unwrap())
+    }
+    /// Converts a null terminated string attribute
+    pub(super) fn string(data: &[u8]) -> String {
+        String::from_utf8_lossy(data)
+            .trim_end_matches('\0')
+            .to_string()
+    }
+    /// Converts an address attribute of the giving address family
+    pub(super) fn ip_address(family: i32, data: &[u8]) -> Option<IpAddress> {
+        match family {
+            libc::AF_INET => Some(IpAddress::from(&IpAddr::V4(Ipv4Addr::from(
+                <[u8; 4]>::try_from(data).ok()?,
+            )))),
+            libc::AF_INET6 => Some(IpAddress::from(&IpAddr::V6(Ipv6Addr::from(
+                <[u8; 16]>::try_from(data).ok()?,
+            )))),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,729 @@
+use crate::core::{Interface, IpAddress, IpVersion};
+use crate::error::{Error, ErrorKind::*};
+use crate::port;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Read, Write};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpStream, UdpSocket};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// The Internet class, the only class yrnu queries
+const CLASS_IN: u16 = 1;
+/// The most compression pointers followed while reading a single name
+const MAX_POINTERS: usize = 64;
+
+/// # RecordType
+/// `RecordType` - type of a DNS resource record
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RecordType {
+    A,
+    Aaaa,
+    Ptr,
+    Mx,
+    Txt,
+    Srv,
+    Ns,
+    Soa,
+    Cname,
+}
+
+/// # Transport
+/// `Transport` - transport protocol the DNS queries are sent over
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Udp,
+    Tcp,
+}
+
+/// # RecordData
+/// `RecordData` - the data of a DNS resource record, decoded by the type of the record
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordData {
+    A(IpAddress),
+    Aaaa(IpAddress),
+    Ptr(String),
+    Mx {
+        preference: u16,
+        exchange: String,
+    },
+    Txt(Vec<String>),
+    Srv {
+        priority: u16,
+        weight: u16,
+        port: u16,
+        target: String,
+    },
+    Ns(String),
+    Soa {
+        mname: String,
+        rname: String,
+        serial: u32,
+        refresh: u32,
+        retry: u32,
+        expire: u32,
+        minimum: u32,
+    },
+    Cname(String),
+    /// A record of a type yrnu does not decode, with its type code and raw data
+    Other {
+        code: u16,
+        data: Vec<u8>,
+    },
+}
+
+/// # Record
+/// `Record` - DNS resource record of a response
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    name: String,
+    ttl: u32,
+    data: RecordData,
+}
+
+/// # Response
+/// `Response` - response of a DNS server to a query, together with the raw message
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response {
+    id: u16,
+    code: u8,
+    authoritative: bool,
+    truncated: bool,
+    recursion_available: bool,
+    transport: Transport,
+    // The (name, type, class) entries of the question section
+    questions: Vec<(String, u16, u16)>,
+    answers: Vec<Record>,
+    authorities: Vec<Record>,
+    additionals: Vec<Record>,
+    raw: Vec<u8>,
+}
+
+/// # Resolver
+/// `Resolver` - DNS client that sends queries to a single name server
+#[derive(Debug, Clone, PartialEq)]
+pub struct Resolver {
+    server: SocketAddr,
+    transport: Transport,
+    timeout: Duration,
+}
+
+/// Cursor over a DNS message, every read returns `None` when the message ends too early
+struct Reader<'a> {
+    message: &'a [u8],
+    offset: usize,
+}
+
+impl RecordType {
+    /// Returns every record type
+    pub fn all() -> [RecordType; 9] {
+        [
+            RecordType::A,
+            RecordType::Aaaa,
+            RecordType::Ptr,
+            RecordType::Mx,
+            RecordType::Txt,
+            RecordType::Srv,
+            RecordType::Ns,
+            RecordType::Soa,
+            RecordType::Cname,
+        ]
+    }
+    /// Returns the type code of the record type (RFC 1035, RFC 3596, RFC 2782)
+    pub fn code(&self) -> u16 {
+        match self {
+            RecordType::A => 1,
+            RecordType::Ns => 2,
+            RecordType::Cname => 5,
+            RecordType::Soa => 6,
+            RecordType::Ptr => 12,
+            RecordType::Mx => 15,
+            RecordType::Txt => 16,
+            RecordType::Aaaa => 28,
+            RecordType::Srv => 33,
+        }
+    }
+    /// Returns the record type of a type code, `None` for the types yrnu does not decode
+    pub fn from_code(code: u16) -> Option<RecordType> {
+        RecordType::all()
+            .into_iter()
+            .find(|kind| kind.code() == code)
+    }
+}
+
+impl Display for RecordType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RecordType::A => write!(f, "A"),
+            RecordType::Aaaa => write!(f, "AAAA"),
+            RecordType::Ptr => write!(f, "PTR"),
+            RecordType::Mx => write!(f, "MX"),
+            RecordType::Txt => write!(f, "TXT"),
+            RecordType::Srv => write!(f, "SRV"),
+            RecordType::Ns => write!(f, "NS"),
+            RecordType::Soa => write!(f, "SOA"),
+            RecordType::Cname => write!(f, "CNAME"),
+        }
+    }
+}
+
+impl FromStr for RecordType {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RecordType::all()
+            .into_iter()
+            .find(|kind| kind.to_string().eq_ignore_ascii_case(s))
+            .ok_or_else(|| Error::new(InvalidRecordType, s))
+    }
+}
+
+impl Display for Transport {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Transport::Udp => write!(f, "udp"),
+            Transport::Tcp => write!(f, "tcp"),
+        }
+    }
+}
+
+impl FromStr for Transport {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "udp" => Ok(Transport::Udp),
+            "tcp" => Ok(Transport::Tcp),
+            _ => Err(Error::new(InvalidProtocol, s)),
+        }
+    }
+}
+
+impl RecordData {
+    /// Returns the type code of the record
+    pub fn code(&self) -> u16 {
+        match self {
+            RecordData::A(_) => RecordType::A.code(),
+            RecordData::Aaaa(_) => RecordType::Aaaa.code(),
+            RecordData::Ptr(_) => RecordType::Ptr.code(),
+            RecordData::Mx { .. } => RecordType::Mx.code(),
+            RecordData::Txt(_) => RecordType::Txt.code(),
+            RecordData::Srv { .. } => RecordType::Srv.code(),
+            RecordData::Ns(_) => RecordType::Ns.code(),
+            RecordData::Soa { .. } => RecordType::Soa.code(),
+            RecordData::Cname(_) => RecordType::Cname.code(),
+            RecordData::Other { code, .. } => *code,
+        }
+    }
+}
+
+impl Display for RecordData {
+    /// Writes the data in the zone file presentation format
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RecordData::A(address) | RecordData::Aaaa(address) => write!(f, "{address}"),
+            RecordData::Ptr(name) | RecordData::Ns(name) | RecordData::Cname(name) => {
+                write!(f, "{name}")
+            }
+            RecordData::Mx {
+                preference,
+                exchange,
+            } => write!(f, "{preference} {exchange}"),
+            RecordData::Txt(strings) => write!(
+                f,
+                "{}",
+                strings
+                    .iter()
+                    .map(|string| format!("{string:?}"))
+                    .collect::<Vec<String>>()
+                    .join(" ")
+            ),
+            RecordData::Srv {
+                priority,
+                weight,
+                port,
+                target,
+            } => write!(f, "{priority} {weight} {port} {target}"),
+            RecordData::Soa {
+                mname,
+                rname,
+                serial,
+                refresh,
+                retry,
+                expire,
+                minimum,
+            } => write!(
+                f,
+                "{mname} {rname} {serial} {refresh} {retry} {expire} {minimum}"
+            ),
+            RecordData::Other { data, .. } => write!(
+                f,
+                "\\# {} {}",
+                data.len(),
+                data.iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect::<String>()
+            ),
+        }
+    }
+}
+
+impl Record {
+    /// Creates a new Record instance
+    pub fn new(name: &str, ttl: u32, data: RecordData) -> Record {
+        Record {
+            name: name.to_string(),
+            ttl,
+            data,
+        }
+    }
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    pub fn ttl(&self) -> u32 {
+        self.ttl
+    }
+    pub fn data(&self) -> &RecordData {
+        &self.data
+    }
+    /// Returns the type of the record, `None` for the types yrnu does not decode
+    pub fn kind(&self) -> Option<RecordType> {
+        RecordType::from_code(self.data.code())
+    }
+    /// Returns the name of the type of the record, like `MX` or `TYPE65` for unknown types
+    pub fn kind_name(&self) -> String {
+        match self.kind() {
+            Some(kind) => kind.to_string(),
+            None => format!("TYPE{}", self.data.code()),
+        }
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} {} IN {} {}",
+            self.name,
+            self.ttl,
+            self.kind_name(),
+            self.data
+        )
+    }
+}
+
+impl Response {
+    /// Parses a DNS response message received over a giving transport
+    pub fn parse(raw: Vec<u8>, transport: Transport) -> Result<Response, Error> {
+        let mut reader = Reader {
+            message: &raw,
+            offset: 0,
+        };
+        let invalid = || Error::new(InvalidDnsResponse, format!("{} bytes", raw.len()));
+        let id = reader.u16().ok_or_else(invalid)?;
+        let flags = reader.u16().ok_or_else(invalid)?;
+        if flags & 0x8000 == 0 {
+            return Err(invalid());
+        }
+        let mut counts = [0u16; 4];
+        for count in counts.iter_mut() {
+            *count = reader.u16().ok_or_else(invalid)?;
+        }
+        let mut questions = vec![];
+        for _ in 0..counts[0] {
+            let question = (|| Some((reader.name()?, reader.u16()?, reader.u16()?)))();
+            questions.push(question.ok_or_else(invalid)?);
+        }
+        let mut sections = [vec![], vec![], vec![]];
+        for (section, count) in sections.iter_mut().zip(&counts[1..]) {
+            for _ in 0..*count {
+                section.push(reader.record().ok_or_else(invalid)?);
+            }
+        }
+        let [answers, authorities, additionals] = sections;
+        Ok(Response {
+            id,
+            code: (flags & 0x000f) as u8,
+            authoritative: flags & 0x0400 != 0,
+            truncated: flags & 0x0200 != 0,
+            recursion_available: flags & 0x0080 != 0,
+            transport,
+            questions,
+            answers,
+            authorities,
+            additionals,
+            raw,
+        })
+    }
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+    /// Returns the response code (RCODE) of the response
+    pub fn code(&self) -> u8 {
+        self.code
+    }
+    /// Returns the name of the response code, like `NOERROR` or `NXDOMAIN`
+    pub fn status(&self) -> String {
+        match self.code {
+            0 => "NOERROR".to_string(),
+            1 => "FORMERR".to_string(),
+            2 => "SERVFAIL".to_string(),
+            3 => "NXDOMAIN".to_string(),
+            4 => "NOTIMP".to_string(),
+            5 => "REFUSED".to_string(),
+            code => format!("RCODE{code}"),
+        }
+    }
+    pub fn is_authoritative(&self) -> bool {
+        self.authoritative
+    }
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+    pub fn is_recursion_available(&self) -> bool {
+        self.recursion_available
+    }
+    /// Returns the transport the response was received over
+    pub fn transport(&self) -> Transport {
+        self.transport
+    }
+    pub fn answers(&self) -> &Vec<Record> {
+        &self.answers
+    }
+    pub fn authorities(&self) -> &Vec<Record> {
+        &self.authorities
+    }
+    pub fn additionals(&self) -> &Vec<Record> {
+        &self.additionals
+    }
+    /// Returns the response message as it was received
+    pub fn raw(&self) -> &Vec<u8> {
+        &self.raw
+    }
+    /// Returns the addresses of the A and AAAA answers
+    pub fn addresses(&self) -> Vec<IpAddress> {
+        self.answers
+            .iter()
+            .filter_map(|record| match &record.data {
+                RecordData::A(address) | RecordData::Aaaa(address) => Some(address.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.status())?;
+        for record in &self.answers {
+            write!(f, "\n{record}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Resolver {
+    /// Creates a new Resolver instance that queries a giving server on the DNS port over UDP
+    /// with a timeout of 5 seconds
+    pub fn new(server: &IpAddress) -> Resolver {
+        let address = match server.version() {
+            IpVersion::V4 => IpAddr::from(Ipv4Addr::from(server.as_u128() as u32)),
+            IpVersion::V6 => IpAddr::from(Ipv6Addr::from(server.as_u128())),
+        };
+        Resolver {
+            server: SocketAddr::new(address, port::DNS),
+            transport: Transport::Udp,
+            timeout: Duration::from_secs(5),
+        }
+    }
+    /// Creates a new Resolver instance for the first name server of `/etc/resolv.conf`
+    pub fn system() -> Result<Resolver, Error> {
+        let path = "/etc/resolv.conf";
+        let config = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(DnsQueryFailed, path).with_source(e))?;
+        Self::parse_resolv_conf(&config).ok_or_else(|| Error::new(DnsQueryFailed, path))
+    }
+    /// Creates a new Resolver instance for the first name server of the `resolv.conf` format, the
+    /// `%scope` of a link local server (an interface name or index) is kept as its scope id
+    pub fn parse_resolv_conf(data: &str) -> Option<Resolver> {
+        data.lines()
+            .filter_map(|line| line.trim().strip_prefix("nameserver"))
+            .find_map(|server| {
+                let (server, scope) = match server.trim().split_once('%') {
+                    Some((server, scope)) => (server, Some(scope)),
+                    None => (server.trim(), None),
+                };
+                let mut resolver = Resolver::new(&IpAddress::from_str(server).ok()?);
+                if let (SocketAddr::V6(address), Some(scope)) = (&mut resolver.server, scope) {
+                    let index = scope.parse::<u32>().ok().or_else(|| {
+                        Interface::by_name(scope)
+                            .ok()
+                            .map(|interface| *interface.index())
+                    })?;
+                    address.set_scope_id(index);
+                }
+                Some(resolver)
+            })
+    }
+    /// Sets the port the server listens on
+    pub fn with_port(mut self, port: u16) -> Resolver {
+        self.server.set_port(port);
+        self
+    }
+    /// Sets the transport the queries are sent over
+    pub fn with_transport(mut self, transport: Transport) -> Resolver {
+        self.transport = transport;
+        self
+    }
+    /// Sets how long to wait for the server before giving up on a query
+    pub fn with_timeout(mut self, timeout: Duration) -> Resolver {
+        self.timeout = timeout;
+        self
+    }
+    pub fn server(&self) -> &SocketAddr {
+        &self.server
+    }
+    pub fn transport(&self) -> Transport {
+        self.transport
+    }
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+    /// Queries the server for the records of a giving type, PTR queries of an ip address are
+    /// sent for its reverse pointer name and a truncated UDP response is queried again over TCP
+    pub fn query(&self, name: &str, kind: RecordType) -> Result<Response, Error> {
+        let name = match IpAddress::from_str(name) {
+            Ok(address) if kind == RecordType::Ptr => address.reverse_pointer(),
+            _ => name.to_string(),
+        };
+        let mut id = [0u8; 2];
+        getrandom::getrandom(&mut id).map_err(|e| Error::new(RandomFailed, "").with_source(e))?;
+        let id = u16::from_ne_bytes(id);
+        let query = Self::encode_query(id, &name, kind)?;
+        let mut response = match self.transport {
+            Transport::Udp => Response::parse(self.send_udp(&query, id)?, Transport::Udp)?,
+            Transport::Tcp => Response::parse(self.send_tcp(&query)?, Transport::Tcp)?,
+        };
+        if response.truncated && response.transport == Transport::Udp {
+            response = Response::parse(self.send_tcp(&query)?, Transport::Tcp)?;
+        }
+        if response.id != id {
+            return Err(Error::new(
+                InvalidDnsResponse,
+                format!("id {}", response.id),
+            ));
+        }
+        // The question has to be echoed back as it was asked (RFC 5452 9.1), names are compared
+        // case insensitively
+        let asked = name.strip_suffix('.').unwrap_or(&name);
+        let asked = if asked.is_empty() { "." } else { asked };
+        match response.questions.as_slice() {
+            [(question, code, CLASS_IN)] if question.eq_ignore_ascii_case(asked) => {
+                if *code != kind.code() {
+                    return Err(Error::new(InvalidDnsResponse, format!("type {code}")));
+                }
+                Ok(response)
+            }
+            [(question, ..)] => Err(Error::new(
+                InvalidDnsResponse,
+                format!("question {question}"),
+            )),
+            questions => Err(Error::new(
+                InvalidDnsResponse,
+                format!("{} questions", questions.len()),
+            )),
+        }
+    }
+    /// Builds a recursive query message of a single question
+    fn encode_query(id: u16, name: &str, kind: RecordType) -> Result<Vec<u8>, Error> {
+        let mut message = Vec::with_capacity(512);
+        message.extend_from_slice(&id.to_be_bytes());
+        // recursion desired, one question
+        message.extend_from_slice(&[0x01, 0x00, 0, 1, 0, 0, 0, 0, 0, 0]);
+        let start = message.len();
+        let labels = name.strip_suffix('.').unwrap_or(name);
+        if !labels.is_empty() {
+            for label in labels.split('.') {
+                if label.is_empty() || label.len() > 63 {
+                    return Err(Error::new(InvalidDomainName, name));
+                }
+                message.push(label.len() as u8);
+                message.extend_from_slice(label.as_bytes());
+            }
+        }
+        message.push(0);
+        if message.len() - start > 255 {
+            return Err(Error::new(InvalidDomainName, name));
+        }
+        message.extend_from_slice(&kind.code().to_be_bytes());
+        message.extend_from_slice(&CLASS_IN.to_be_bytes());
+        Ok(message)
+    }
+    /// The error of a query that could not be sent or was not answered
+    fn failure(&self, error: io::Error) -> Error {
+        let error = match error.kind() {
+            // a read timeout is reported as `WouldBlock` on unix
+            io::ErrorKind::WouldBlock => io::Error::from(io::ErrorKind::TimedOut),
+            _ => error,
+        };
+        Error::new(DnsQueryFailed, self.server).with_source(error)
+    }
+    /// Sends a query over UDP, the responses of other queries are ignored
+    fn send_udp(&self, query: &[u8], id: u16) -> Result<Vec<u8>, Error> {
+        let local = match self.server {
+            SocketAddr::V4(_) => SocketAddr::from(([0, 0, 0, 0], 0)),
+            SocketAddr::V6(_) => SocketAddr::from(([0u16; 8], 0)),
+        };
+        let socket = UdpSocket::bind(local).map_err(|e| self.failure(e))?;
+        socket.connect(self.server).map_err(|e| self.failure(e))?;
+        socket.send(query).map_err(|e| self.failure(e))?;
+        let deadline = Instant::now() + self.timeout;
+        let mut buffer = vec![0u8; u16::MAX as usize];
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            if left.is_zero() {
+                return Err(self.failure(io::Error::from(io::ErrorKind::TimedOut)));
+            }
+            socket
+                .set_read_timeout(Some(left))
+                .map_err(|e| self.failure(e))?;
+            let length = socket.recv(&mut buffer).map_err(|e| self.failure(e))?;
+            if buffer[..length].starts_with(&id.to_be_bytes()) {
+                return Ok(buffer[..length].to_vec());
+            }
+        }
+    }
+    /// Sends a query over TCP, the messages are prefixed with their length
+    fn send_tcp(&self, query: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut stream =
+            TcpStream::connect_timeout(&self.server, self.timeout).map_err(|e| self.failure(e))?;
+        stream
+            .set_read_timeout(Some(self.timeout))
+            .and_then(|_| stream.set_write_timeout(Some(self.timeout)))
+            .map_err(|e| self.failure(e))?;
+        let mut message = (query.len() as u16).to_be_bytes().to_vec();
+        message.extend_from_slice(query);
+        stream.write_all(&message).map_err(|e| self.failure(e))?;
+        let mut length = [0u8; 2];
+        stream
+            .read_exact(&mut length)
+            .map_err(|e| self.failure(e))?;
+        let mut response = vec![0u8; u16::from_be_bytes(length) as usize];
+        stream
+            .read_exact(&mut response)
+            .map_err(|e| self.failure(e))?;
+        Ok(response)
+    }
+}
+
+impl Reader<'_> {
+    fn bytes(&mut self, length: usize) -> Option<&[u8]> {
+        let bytes = self
+            .message
+            .get(self.offset..self.offset.checked_add(length)?)?;
+        self.offset += length;
+        Some(bytes)
+    }
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.bytes(1)?[0])
+    }
+    fn u16(&mut self) -> Option<u16> {
+        Some(u16::from_be_bytes(self.bytes(2)?.try_into().ok()?))
+    }
+    fn u32(&mut self) -> Option<u32> {
+        Some(u32::from_be_bytes(self.bytes(4)?.try_into().ok()?))
+    }
+    /// Reads a possibly compressed domain name (RFC 1035 4.1.4), the root is read as `.`
+    fn name(&mut self) -> Option<String> {
+        let mut labels = vec![];
+        let mut offset = self.offset;
+        let mut pointers = 0;
+        loop {
+            let length = *self.message.get(offset)? as usize;
+            match length & 0xc0 {
+                0x00 if length == 0 => {
+                    if pointers == 0 {
+                        self.offset = offset + 1;
+                    }
+                    break;
+                }
+                0x00 => {
+                    let label = self.message.get(offset + 1..offset + 1 + length)?;
+                    labels.push(String::from_utf8_lossy(label).into_owned());
+                    offset += 1 + length;
+                }
+                0xc0 => {
+                    if pointers == MAX_POINTERS {
+                        return None;
+                    }
+                    if pointers == 0 {
+                        self.offset = offset + 2;
+                    }
+                    pointers += 1;
+                    offset = ((length & 0x3f) << 8) | *self.message.get(offset + 1)? as usize;
+                }
+                _ => return None,
+            }
+        }
+        Some(if labels.is_empty() {
+            ".".to_string()
+        } else {
+            labels.join(".")
+        })
+    }
+    /// Reads a resource record, the record has to fit in its data length
+    fn record(&mut self) -> Option<Record> {
+        let name = self.name()?;
+        let code = self.u16()?;
+        let _class = self.u16()?;
+        let ttl = self.u32()?;
+        let length = self.u16()? as usize;
+        let end = self.offset + length;
+        if end > self.message.len() {
+            return None;
+        }
+        let data = match RecordType::from_code(code) {
+            Some(RecordType::A) if length == 4 => {
+                RecordData::A(IpAddress::new(&self.bytes(4)?.to_vec()).ok()?)
+            }
+            Some(RecordType::Aaaa) if length == 16 => {
+                RecordData::Aaaa(IpAddress::new(&self.bytes(16)?.to_vec()).ok()?)
+            }
+            Some(RecordType::Ptr) => RecordData::Ptr(self.name()?),
+            Some(RecordType::Ns) => RecordData::Ns(self.name()?),
+            Some(RecordType::Cname) => RecordData::Cname(self.name()?),
+            Some(RecordType::Mx) => RecordData::Mx {
+                preference: self.u16()?,
+                exchange: self.name()?,
+            },
+            Some(RecordType::Txt) => {
+                let mut strings = vec![];
+                while self.offset < end {
+                    let length = self.u8()? as usize;
+                    strings.push(String::from_utf8_lossy(self.bytes(length)?).into_owned());
+                }
+                RecordData::Txt(strings)
+            }
+            Some(RecordType::Srv) => RecordData::Srv {
+                priority: self.u16()?,
+                weight: self.u16()?,
+                port: self.u16()?,
+                target: self.name()?,
+            },
+            Some(RecordType::Soa) => RecordData::Soa {
+                mname: self.name()?,
+                rname: self.name()?,
+                serial: self.u32()?,
+                refresh: self.u32()?,
+                retry: self.u32()?,
+                expire: self.u32()?,
+                minimum: self.u32()?,
+            },
+            _ => RecordData::Other {
+                code,
+                data: self.bytes(length)?.to_vec(),
+            },
+        };
+        if self.offset != end {
+            return None;
+        }
+        Some(Record { name, ttl, data })
+    }
+}
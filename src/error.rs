@@ -1 +1,241 @@
-pub mod coreerr;
+use std::error::Error as StdError;
+use std::fmt::{Debug, Display, Formatter, Result};
+use std::panic::Location;
+
+/// # ErrorKind
+/// `ErrorKind` - what went wrong, every kind belongs to one of the yrnu subsystems
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    // core
+    InvalidIpAddress,
+    InvalidIpV6Address,
+    InvalidMacAddress,
+    InvalidMask,
+    InvalidPrefix,
+    InvalidNetwork,
+    InterfaceNotExists,
+    InterfaceConversionFailed,
+    InsufficientAddressSpace,
+    AddressOverflow,
+    InvalidIpRange,
+    InvalidWildcardMask,
+    InvalidOuiDatabase,
+    InterfaceWatchFailed,
+    InterfaceEventsLost,
+    InvalidInterfaceAddress,
+    InvalidPort,
+    InvalidPortRange,
+    InvalidProtocol,
+    InvalidServicesFile,
+    InvalidDomainName,
+    InvalidRecordType,
+    DnsQueryFailed,
+    InvalidDnsResponse,
+    RandomFailed,
+    // config
+    ConnectionFailed,
+    AuthenticationFailed,
+    CommandFailed,
+    // parser
+    InvalidJson,
+    InvalidYaml,
+    InvalidToml,
+    InvalidXml,
+    InvalidCsv,
+    UnsupportedFormat,
+    // plugin
+    PluginLoadFailed,
+    ScriptFailed,
+}
+
+/// # Error
+/// `Error` - the error of every yrnu subsystem, carrying the kind of the error, the input that
+/// caused it, the underlying error if any and the place it was raised at
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    input: String,
+    source: Option<Box<dyn StdError + Send + Sync>>,
+    location: &'static Location<'static>,
+}
+
+impl ErrorKind {
+    /// Returns the name of the kind in snake case, like `invalid_ip_address`
+    pub fn name(&self) -> &'static str {
+        match self {
+            ErrorKind::InvalidIpAddress => "invalid_ip_address",
+            ErrorKind::InvalidIpV6Address => "invalid_ipv6_address",
+            ErrorKind::InvalidMacAddress => "invalid_mac_address",
+            ErrorKind::InvalidMask => "invalid_mask",
+            ErrorKind::InvalidPrefix => "invalid_prefix",
+            ErrorKind::InvalidNetwork => "invalid_network",
+            ErrorKind::InterfaceNotExists => "interface_not_exists",
+            ErrorKind::InterfaceConversionFailed => "interface_conversion_failed",
+            ErrorKind::InsufficientAddressSpace => "insufficient_address_space",
+            ErrorKind::AddressOverflow => "address_overflow",
+            ErrorKind::InvalidIpRange => "invalid_ip_range",
+            ErrorKind::InvalidWildcardMask => "invalid_wildcard_mask",
+            ErrorKind::InvalidOuiDatabase => "invalid_oui_database",
+            ErrorKind::InterfaceWatchFailed => "interface_watch_failed",
+            ErrorKind::InterfaceEventsLost => "interface_events_lost",
+            ErrorKind::InvalidInterfaceAddress => "invalid_interface_address",
+            ErrorKind::InvalidPort => "invalid_port",
+            ErrorKind::InvalidPortRange => "invalid_port_range",
+            ErrorKind::InvalidProtocol => "invalid_protocol",
+            ErrorKind::InvalidServicesFile => "invalid_services_file",
+            ErrorKind::InvalidDomainName => "invalid_domain_name",
+            ErrorKind::InvalidRecordType => "invalid_record_type",
+            ErrorKind::DnsQueryFailed => "dns_query_failed",
+            ErrorKind::InvalidDnsResponse => "invalid_dns_response",
+            ErrorKind::RandomFailed => "random_failed",
+            ErrorKind::ConnectionFailed => "connection_failed",
+            ErrorKind::AuthenticationFailed => "authentication_failed",
+            ErrorKind::CommandFailed => "command_failed",
+            ErrorKind::InvalidJson => "invalid_json",
+            ErrorKind::InvalidYaml => "invalid_yaml",
+            ErrorKind::InvalidToml => "invalid_toml",
+            ErrorKind::InvalidXml => "invalid_xml",
+            ErrorKind::InvalidCsv => "invalid_csv",
+            ErrorKind::UnsupportedFormat => "unsupported_format",
+            ErrorKind::PluginLoadFailed => "plugin_load_failed",
+            ErrorKind::ScriptFailed => "script_failed",
+        }
+    }
+    /// Returns the name of the subsystem the kind belongs to, one of `core`, `config`, `parser`
+    /// or `plugin`
+    pub fn subsystem(&self) -> &'static str {
+        match self {
+            ErrorKind::ConnectionFailed
+            | ErrorKind::AuthenticationFailed
+            | ErrorKind::CommandFailed => "config",
+            ErrorKind::InvalidJson
+            | ErrorKind::InvalidYaml
+            | ErrorKind::InvalidToml
+            | ErrorKind::InvalidXml
+            | ErrorKind::InvalidCsv
+            | ErrorKind::UnsupportedFormat => "parser",
+            ErrorKind::PluginLoadFailed | ErrorKind::ScriptFailed => "plugin",
+            _ => "core",
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let message = match self {
+            ErrorKind::InvalidIpAddress => "An invalid ip address",
+            ErrorKind::InvalidIpV6Address => "An invalid ipv6 address",
+            ErrorKind::InvalidMacAddress => "An invalid mac address",
+            ErrorKind::InvalidMask => "An invalid subnet mask",
+            ErrorKind::InvalidPrefix => "An invalid mask prefix",
+            ErrorKind::InvalidNetwork => "An invalid ip network",
+            ErrorKind::InterfaceNotExists => "Interface doesn't exists",
+            ErrorKind::InterfaceConversionFailed => {
+                "Failed converting Interface into NetworkInterface"
+            }
+            ErrorKind::InsufficientAddressSpace => "Not enough address space in the network",
+            ErrorKind::AddressOverflow => "Ip address arithmetic overflowed the address range",
+            ErrorKind::InvalidIpRange => "An invalid ip range",
+            ErrorKind::InvalidWildcardMask => "An invalid wildcard mask",
+            ErrorKind::InvalidOuiDatabase => "An invalid mac address vendor database",
+            ErrorKind::InterfaceWatchFailed => "Failed to watch the network interfaces",
+            ErrorKind::InterfaceEventsLost => {
+                "Interface events were lost, the kernel receive queue overran"
+            }
+            ErrorKind::InvalidInterfaceAddress => "An invalid interface address",
+            ErrorKind::InvalidPort => "An invalid port",
+            ErrorKind::InvalidPortRange => "An invalid port range",
+            ErrorKind::InvalidProtocol => "An invalid transport protocol",
+            ErrorKind::InvalidServicesFile => "An invalid services file",
+            ErrorKind::InvalidDomainName => "An invalid domain name",
+            ErrorKind::InvalidRecordType => "An invalid DNS record type",
+            ErrorKind::DnsQueryFailed => "Failed to query the DNS server",
+            ErrorKind::InvalidDnsResponse => "An invalid DNS response",
+            ErrorKind::RandomFailed => "Failed to get random bytes from the OS",
+            ErrorKind::ConnectionFailed => "Failed to connect",
+            ErrorKind::AuthenticationFailed => "Failed to authenticate",
+            ErrorKind::CommandFailed => "Failed to run the command",
+            ErrorKind::InvalidJson => "An invalid JSON",
+            ErrorKind::InvalidYaml => "An invalid YAML",
+            ErrorKind::InvalidToml => "An invalid TOML",
+            ErrorKind::InvalidXml => "An invalid XML",
+            ErrorKind::InvalidCsv => "An invalid CSV",
+            ErrorKind::UnsupportedFormat => "An unsupported format",
+            ErrorKind::PluginLoadFailed => "Failed to load the plugin",
+            ErrorKind::ScriptFailed => "The script failed",
+        };
+        write!(f, "{message}")
+    }
+}
+
+impl Error {
+    /// Creates a new Error instance raised at the caller
+    #[track_caller]
+    pub fn new(kind: ErrorKind, input: impl ToString) -> Error {
+        Error {
+            kind,
+            input: input.to_string(),
+            source: None,
+            location: Location::caller(),
+        }
+    }
+    /// Sets the underlying error that caused this one
+    pub fn with_source(mut self, source: impl Into<Box<dyn StdError + Send + Sync>>) -> Error {
+        self.source = Some(source.into());
+        self
+    }
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+    /// Returns the input that caused the error, empty when there is none
+    pub fn input(&self) -> &String {
+        &self.input
+    }
+    /// Returns the place in the source code the error was raised at
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+    /// Returns the name of the subsystem the kind of the error belongs to
+    pub fn subsystem(&self) -> &'static str {
+        self.kind.subsystem()
+    }
+}
+
+impl Display for Error {
+    /// Writes the kind and the input, the alternate form (`{:#}`) adds the chain of sources
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}", self.kind)?;
+        if !self.input.is_empty() {
+            write!(f, " `{}`", self.input)?;
+        }
+        if f.alternate() {
+            let mut source = self.source();
+            while let Some(error) = source {
+                write!(f, ": {error}")?;
+                source = error.source();
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        self.source
+            .as_ref()
+            .map(|source| source.as_ref() as &(dyn StdError + 'static))
+    }
+}
+
+impl From<mlua::Error> for Error {
+    #[track_caller]
+    fn from(error: mlua::Error) -> Error {
+        Error::new(ErrorKind::ScriptFailed, "").with_source(error)
+    }
+}
+
+impl From<Error> for mlua::Error {
+    fn from(error: Error) -> mlua::Error {
+        mlua::Error::external(error)
+    }
+}
@@ -3,12 +3,35 @@
 //! it provides countless utils for packet analysis, network device configurations and other utils
 //! for automating network and cyber security tasks.
 
+/// Implements the string based serde representation of types through their `Display` and
+/// `FromStr` implementations
+#[cfg(feature = "serde")]
+macro_rules! serde_from_str {
+    ($($ty:ty),+) => {
+        $(
+            impl serde::Serialize for $ty {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+            impl<'de> serde::Deserialize<'de> for $ty {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let value = String::deserialize(deserializer)?;
+                    value.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )+
+    };
+}
+
 pub mod config;
 /// A module that provides tools for handling IP and MAC addresses as well as tools to define
 /// networks
 #[warn(unused)]
 pub mod core;
+pub mod dns;
 pub mod error;
+pub use error::{Error, ErrorKind};
 pub mod lua;
 pub mod packet;
 pub mod parser;
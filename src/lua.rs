@@ -1,7 +1,7 @@
 pub mod core_lua;
 pub mod interpreter;
 use crate::core::*;
-use crate::port;
+use crate::port::{self, PortRange, Service};
 use mlua::{Lua, Result, StdLib};
 trait LuaSetup {
     fn setup(lua: &mlua::Lua) -> Result<()>;
@@ -28,6 +28,9 @@ pub fn ports_setup(lua: &mlua::Lua) -> Result<()> {
     ports_table.set("rip", port::RIP)?;
     ports_table.set("ftps", port::FTPS)?;
     ports_table.set("ftps_data", port::FTPS_DATA)?;
+    let metatable = lua.create_table()?;
+    metatable.set("__index", lua.create_function(core_lua::port_by_name)?)?;
+    ports_table.set_metatable(Some(metatable));
     lua.globals().set("port", ports_table)?;
     Ok(())
 }
@@ -39,9 +42,19 @@ pub fn init() -> Result<Lua> {
     _ = IpKind::setup(&lua);
     _ = IpAddress::setup(&lua);
     _ = Mask::setup(&lua);
+    _ = WildcardMask::setup(&lua);
     _ = Network::setup(&lua);
+    _ = IpRange::setup(&lua);
+    _ = IpSet::setup(&lua);
     _ = MacAddress::setup(&lua);
+    _ = MacNotation::setup(&lua);
     _ = Interface::setup(&lua);
+    _ = InterfaceState::setup(&lua);
+    _ = InterfaceAddress::setup(&lua);
+    _ = Route::setup(&lua);
+    _ = Neighbor::setup(&lua);
+    _ = Service::setup(&lua);
+    _ = PortRange::setup(&lua);
     _ = Path::setup(&lua);
     _ = Url::setup(&lua);
     Ok(lua)
@@ -1,12 +1,41 @@
 use super::LuaSetup;
 use crate::core::*;
-use mlua::{IntoLua, MetaMethod, Result, UserData, UserDataMethods};
+use crate::dns::{Record, RecordData, RecordType, Resolver, Response, Transport};
+use crate::error::{Error, ErrorKind::*};
+use crate::port::{PortRange, Protocol, Service, parse_port};
+use mlua::{FromLua, IntoLua, IntoLuaMulti, MetaMethod, Result, UserData, UserDataMethods};
 use regex::Regex;
 use std::fs::{File, rename};
 use std::io::{BufRead, BufReader, Read, Write};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+/// Converts a result into the Lua return values, the value on success and `nil` with the error
+/// on failure
+fn lua_result<T>(result: std::result::Result<T, Error>) -> (Option<T>, Option<Error>) {
+    match result {
+        Ok(value) => (Some(value), None),
+        Err(e) => (None, Some(e)),
+    }
+}
+
+impl UserData for Error {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("kind", |_, this| Ok(this.kind().name()));
+        fields.add_field_method_get("subsystem", |_, this| Ok(this.subsystem()));
+        fields.add_field_method_get("message", |_, this| Ok(this.kind().to_string()));
+        fields.add_field_method_get("input", |_, this| Ok(this.input().to_owned()));
+        fields.add_field_method_get("source", |_, this| {
+            Ok(std::error::Error::source(this).map(|source| source.to_string()))
+        });
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| {
+            Ok(format!("{:#}", this))
+        });
+    }
+}
+
 impl UserData for IpVersion {
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_meta_method(MetaMethod::ToString, |_, this, ()| {
@@ -53,6 +82,15 @@ impl LuaSetup for IpKind {
         kinds_table.set("netid", IpKind::Netid)?;
         kinds_table.set("multicast", IpKind::Multicast)?;
         kinds_table.set("unspecified", IpKind::Unspecified)?;
+        kinds_table.set("shared", IpKind::Shared)?;
+        kinds_table.set("documentation", IpKind::Documentation)?;
+        kinds_table.set("benchmarking", IpKind::Benchmarking)?;
+        kinds_table.set("reserved", IpKind::Reserved)?;
+        kinds_table.set("sixtofour", IpKind::SixToFour)?;
+        kinds_table.set("teredo", IpKind::Teredo)?;
+        kinds_table.set("ipv4mapped", IpKind::Ipv4Mapped)?;
+        kinds_table.set("orchid", IpKind::Orchid)?;
+        kinds_table.set("special", IpKind::Special)?;
         kinds_table.set(
             "is_public",
             lua.create_function(|_, address: String| Ok(IpKind::is_public(address.as_str())))?,
@@ -81,14 +119,27 @@ impl LuaSetup for IpKind {
             "is_unspecified",
             lua.create_function(|_, address: String| Ok(IpKind::is_unspecified(address.as_str())))?,
         )?;
+        kinds_table.set(
+            "is_forwardable",
+            lua.create_function(|_, address: String| Ok(IpKind::is_forwardable(address.as_str())))?,
+        )?;
+        kinds_table.set(
+            "is_reserved_by_protocol",
+            lua.create_function(|_, address: String| {
+                Ok(IpKind::is_reserved_by_protocol(address.as_str()))
+            })?,
+        )?;
+        kinds_table.set(
+            "special_purpose",
+            lua.create_function(|_, address: String| {
+                Ok(IpKind::special_purpose(address.as_str()))
+            })?,
+        )?;
         kinds_table.set(
             "get_kind",
-            lua.create_function(
-                |_, address: String| match IpKind::get_kind(address.as_str()) {
-                    Ok(kind) => Ok(Some(kind)),
-                    Err(_) => Ok(None),
-                },
-            )?,
+            lua.create_function(|_, address: String| {
+                Ok(lua_result(IpKind::get_kind(address.as_str())))
+            })?,
         )?;
         kinds_table.set(
             "is_broadcast",
@@ -105,10 +156,7 @@ impl LuaSetup for IpKind {
         kinds_table.set(
             "get_broadcast",
             lua.create_function(|_, (id, mask): (String, Mask)| {
-                match IpKind::get_broadcast(id.as_str(), &mask) {
-                    Ok(broadcast) => Ok(Some(broadcast)),
-                    Err(_) => Ok(None),
-                }
+                Ok(lua_result(IpKind::get_broadcast(id.as_str(), &mask)))
             })?,
         )?;
         let _ = lua.globals().set("IpKind", kinds_table);
@@ -116,6 +164,28 @@ impl LuaSetup for IpKind {
     }
 }
 
+impl UserData for SpecialPurpose {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("network", |_, this| Ok(this.network().to_owned()));
+        fields.add_field_method_get("name", |_, this| Ok(this.name().to_owned()));
+        fields.add_field_method_get("rfc", |_, this| Ok(this.rfc().to_owned()));
+        fields.add_field_method_get("kind", |_, this| Ok(this.kind().to_owned()));
+        fields.add_field_method_get("source", |_, this| Ok(this.is_source()));
+        fields.add_field_method_get("destination", |_, this| Ok(this.is_destination()));
+        fields.add_field_method_get("forwardable", |_, this| Ok(this.is_forwardable()));
+        fields.add_field_method_get("global", |_, this| Ok(this.is_global()));
+        fields.add_field_method_get("reserved_by_protocol", |_, this| {
+            Ok(this.is_reserved_by_protocol())
+        });
+        fields.add_field_method_get("deprecated", |_, this| Ok(this.is_deprecated()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| {
+            Ok(format!("{} ({})", this.name(), this.network()))
+        });
+    }
+}
+
 impl UserData for IpAddress {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("address", |_, this| Ok(this.address().to_owned()));
@@ -124,11 +194,85 @@ impl UserData for IpAddress {
     }
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_method("octets", |_, this, ()| Ok(this.octets().clone()));
-        methods.add_method("get_expended", |_, this, ()| match this.get_expended() {
-            Ok(addr) => Ok(Some(addr)),
-            Err(_) => Ok(None),
+        methods.add_method("get_expended", |_, this, ()| {
+            Ok(lua_result(this.get_expended()))
+        });
+        methods.add_method("reverse_pointer", |_, this, ()| Ok(this.reverse_pointer()));
+        methods.add_method("to_ipv4_mapped", |_, this, ()| {
+            Ok(lua_result(this.to_ipv4_mapped()))
+        });
+        methods.add_method("extract_ipv4_mapped", |_, this, ()| {
+            Ok(lua_result(this.extract_ipv4_mapped()))
+        });
+        // The NAT64 prefix is the well-known prefix 64:ff9b::/96 by default
+        methods.add_method("to_nat64", |_, this, prefix: Option<Network>| {
+            let prefix = prefix.unwrap_or(Network::from_str("64:ff9b::/96").unwrap());
+            Ok(lua_result(this.to_nat64(&prefix)))
+        });
+        methods.add_method("extract_nat64", |_, this, prefix: Option<Network>| {
+            let prefix = prefix.unwrap_or(Network::from_str("64:ff9b::/96").unwrap());
+            Ok(lua_result(this.extract_nat64(&prefix)))
+        });
+        methods.add_method("to_6to4", |_, this, ()| Ok(lua_result(this.to_6to4())));
+        methods.add_method("extract_6to4", |_, this, ()| {
+            Ok(lua_result(this.extract_6to4()))
+        });
+        methods.add_method("to_isatap", |_, this, prefix: Network| {
+            Ok(lua_result(this.to_isatap(&prefix)))
+        });
+        methods.add_method("extract_isatap", |_, this, ()| {
+            Ok(lua_result(this.extract_isatap()))
+        });
+        methods.add_method("solicited_node", |_, this, ()| {
+            Ok(lua_result(this.solicited_node()))
+        });
+        methods.add_method("multicast_mac", |_, this, ()| {
+            Ok(lua_result(this.multicast_mac()))
+        });
+        methods.add_method("next", |_, this, ()| Ok(lua_result(this.next())));
+        methods.add_method("prev", |_, this, ()| Ok(lua_result(this.prev())));
+        methods.add_method("distance", |_, this, other: IpAddress| {
+            Ok(lua_result(this.distance(&other)))
         });
         methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_function(
+            MetaMethod::Add,
+            |lua, (lhs, rhs): (mlua::Value, mlua::Value)| {
+                // the offset can be on either side, `ip + 1` and `1 + ip`
+                let (this, offset) = match IpAddress::from_lua(lhs.clone(), lua) {
+                    Ok(this) => (this, i64::from_lua(rhs, lua)?),
+                    Err(_) => (IpAddress::from_lua(rhs, lua)?, i64::from_lua(lhs, lua)?),
+                };
+                Ok(lua_result(if offset >= 0 {
+                    this.checked_add(offset as u128)
+                } else {
+                    this.checked_sub(offset.unsigned_abs() as u128)
+                }))
+            },
+        );
+        methods.add_meta_method(MetaMethod::Sub, |lua, this, other: mlua::Value| {
+            if let Ok(other) = IpAddress::from_lua(other.clone(), lua) {
+                lua_result(other.distance(this)).into_lua_multi(lua)
+            } else {
+                let offset = i64::from_lua(other, lua)?;
+                lua_result(if offset >= 0 {
+                    this.checked_sub(offset as u128)
+                } else {
+                    this.checked_add(offset.unsigned_abs() as u128)
+                })
+                .into_lua_multi(lua)
+            }
+        });
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: IpAddress| {
+            Ok(this == &other)
+        });
+        methods.add_meta_method(
+            MetaMethod::Lt,
+            |_, this, other: IpAddress| Ok(this < &other),
+        );
+        methods.add_meta_method(MetaMethod::Le, |_, this, other: IpAddress| {
+            Ok(this <= &other)
+        });
     }
 }
 impl LuaSetup for IpAddress {
@@ -136,25 +280,16 @@ impl LuaSetup for IpAddress {
         let constructor =
             lua.create_function(
                 |_, (_, address): (mlua::Value, mlua::Value)| match address {
-                    mlua::Value::Table(table) => {
-                        match IpAddress::new(
-                            &table
-                                .sequence_values::<u8>()
-                                .filter(|n| n.is_ok())
-                                .map(|n| n.unwrap())
-                                .collect::<Vec<u8>>(),
-                        ) {
-                            Ok(mask) => Ok(Some(mask)),
-                            Err(_) => Ok(None),
-                        }
-                    }
+                    mlua::Value::Table(table) => Ok(lua_result(IpAddress::new(
+                        &table.sequence_values::<u8>().flatten().collect::<Vec<u8>>(),
+                    ))),
                     mlua::Value::String(address) => {
-                        match IpAddress::from_str(&address.to_string_lossy()) {
-                            Ok(addr) => Ok(Some(addr)),
-                            Err(_) => Ok(None),
-                        }
+                        Ok(lua_result(IpAddress::from_str(&address.to_string_lossy())))
                     }
-                    _ => Ok(None),
+                    _ => Ok((
+                        None,
+                        Some(Error::new(InvalidIpAddress, address.type_name())),
+                    )),
                 },
             )?;
         let ipaddress_table = lua.create_table()?;
@@ -167,30 +302,60 @@ impl LuaSetup for IpAddress {
         )?;
         _ = ipaddress_table.set(
             "expend",
-            lua.create_function(
-                |_, address: String| match IpAddress::expend(address.as_str()) {
-                    Ok(addr) => Ok(Some(addr)),
-                    Err(_) => Ok(None),
-                },
-            )?,
+            lua.create_function(|_, address: String| {
+                Ok(lua_result(IpAddress::expend(address.as_str())))
+            })?,
         );
         _ = ipaddress_table.set(
             "shorten",
-            lua.create_function(
-                |_, address: String| match IpAddress::shorten(address.as_str()) {
-                    Ok(addr) => Ok(Some(addr)),
-                    Err(_) => Ok(None),
-                },
-            )?,
+            lua.create_function(|_, address: String| {
+                Ok(lua_result(IpAddress::shorten(address.as_str())))
+            })?,
         );
         ipaddress_table.set(
             "eui64",
             lua.create_function(|_, address: MacAddress| Ok(IpAddress::eui64(&address)))?,
         )?;
+        ipaddress_table.set(
+            "stable_privacy",
+            lua.create_function(
+                |_,
+                 (prefix, interface, network_id, dad_counter, secret): (
+                    Network,
+                    String,
+                    Option<String>,
+                    Option<u8>,
+                    mlua::String,
+                )| {
+                    Ok(lua_result(IpAddress::stable_privacy(
+                        &prefix,
+                        &interface,
+                        &network_id.unwrap_or_default(),
+                        dad_counter.unwrap_or(0),
+                        &secret.as_bytes(),
+                    )))
+                },
+            )?,
+        )?;
+        ipaddress_table.set(
+            "temporary",
+            lua.create_function(|_, (prefix, secret): (Network, mlua::String)| {
+                Ok(lua_result(IpAddress::temporary(
+                    &prefix,
+                    &secret.as_bytes(),
+                )))
+            })?,
+        )?;
         ipaddress_table.set(
             "from_domain",
             lua.create_function(|_, domain: String| Ok(IpAddress::from_domain(&domain)))?,
         )?;
+        ipaddress_table.set(
+            "from_reverse_pointer",
+            lua.create_function(|_, name: String| {
+                Ok(lua_result(IpAddress::from_reverse_pointer(&name)))
+            })?,
+        )?;
         let _ = lua.globals().set("IpAddress", ipaddress_table);
         Ok(())
     }
@@ -201,6 +366,7 @@ impl UserData for Mask {
         fields.add_field_method_get("mask", |_, this| Ok(this.mask().to_owned()));
         fields.add_field_method_get("prefix", |_, this| Ok(this.prefix().to_owned()));
         fields.add_field_method_get("num_of_hosts", |_, this| Ok(this.num_of_hosts().to_owned()));
+        fields.add_field_method_get("version", |_, this| Ok(this.version().to_owned()));
     }
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_method("wildcard", |_, this, ()| Ok(this.wildcard()));
@@ -211,23 +377,13 @@ impl LuaSetup for Mask {
     fn setup(lua: &mlua::Lua) -> Result<()> {
         let constructor =
             lua.create_function(|_, (_, mask): (mlua::Value, mlua::Value)| match mask {
-                mlua::Value::Table(table) => {
-                    match Mask::new(
-                        &table
-                            .sequence_values::<u8>()
-                            .filter(|n| n.is_ok())
-                            .map(|n| n.unwrap())
-                            .collect::<Vec<u8>>(),
-                    ) {
-                        Ok(mask) => Ok(Some(mask)),
-                        Err(_) => Ok(None),
-                    }
+                mlua::Value::Table(table) => Ok(lua_result(Mask::new(
+                    &table.sequence_values::<u8>().flatten().collect::<Vec<u8>>(),
+                ))),
+                mlua::Value::String(mask) => {
+                    Ok(lua_result(Mask::from_str(&mask.to_string_lossy())))
                 }
-                mlua::Value::String(mask) => match Mask::from_str(&mask.to_string_lossy()) {
-                    Ok(mask) => Ok(Some(mask)),
-                    Err(_) => Ok(None),
-                },
-                _ => Ok(None),
+                _ => Ok((None, Some(Error::new(InvalidMask, mask.type_name())))),
             })?;
         let mask_table = lua.create_table()?;
         let metatable = lua.create_table()?;
@@ -239,20 +395,18 @@ impl LuaSetup for Mask {
         )?;
         mask_table.set(
             "from_prefix",
-            lua.create_function(|_, prefix: u8| match Mask::from_prefix(prefix) {
-                Ok(mask) => Ok(Some(mask)),
-                Err(_) => Ok(None),
+            lua.create_function(|_, (prefix, version): (u8, Option<IpVersion>)| {
+                Ok(lua_result(Mask::from_version_prefix(
+                    prefix,
+                    &version.unwrap_or(IpVersion::V4),
+                )))
             })?,
         )?;
         mask_table.set(
             "get_prefix",
             lua.create_function(|_, mask: mlua::Value| match mask {
                 mlua::Value::Table(table) => Ok(Some(Mask::get_prefix(
-                    &table
-                        .sequence_values::<u8>()
-                        .filter(|n| n.is_ok())
-                        .map(|n| n.unwrap())
-                        .collect::<Vec<u8>>(),
+                    &table.sequence_values::<u8>().flatten().collect::<Vec<u8>>(),
                 ))),
                 mlua::Value::String(mask) => {
                     let octets = IpAddress::octets_from_str(&mask.to_string_lossy());
@@ -269,9 +423,62 @@ impl LuaSetup for Mask {
     }
 }
 
+impl UserData for WildcardMask {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("version", |_, this| Ok(this.version().to_owned()));
+        fields.add_field_method_get("num_of_addresses", |_, this| Ok(this.num_of_addresses()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: WildcardMask| {
+            Ok(this == &other)
+        });
+        methods.add_method("octets", |_, this, ()| Ok(this.octets()));
+        methods.add_method("is_contiguous", |_, this, ()| Ok(this.is_contiguous()));
+        methods.add_method("to_mask", |_, this, ()| Ok(lua_result(this.to_mask())));
+        methods.add_method(
+            "matches",
+            |_, this, (base, address): (IpAddress, IpAddress)| Ok(this.matches(&base, &address)),
+        );
+    }
+}
+impl LuaSetup for WildcardMask {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let constructor =
+            lua.create_function(
+                |_, (_, wildcard): (mlua::Value, mlua::Value)| match wildcard {
+                    mlua::Value::Table(table) => Ok(lua_result(WildcardMask::new(
+                        &table.sequence_values::<u8>().flatten().collect::<Vec<u8>>(),
+                    ))),
+                    mlua::Value::String(wildcard) => Ok(lua_result(WildcardMask::from_str(
+                        &wildcard.to_string_lossy(),
+                    ))),
+                    _ => Ok((
+                        None,
+                        Some(Error::new(InvalidWildcardMask, wildcard.type_name())),
+                    )),
+                },
+            )?;
+        let wildcard_table = lua.create_table()?;
+        let metatable = lua.create_table()?;
+        metatable.set("__call", constructor)?;
+        wildcard_table.set_metatable(Some(metatable));
+        wildcard_table.set(
+            "is_valid",
+            lua.create_function(|_, wildcard: String| Ok(WildcardMask::is_valid(&wildcard)))?,
+        )?;
+        wildcard_table.set(
+            "from_mask",
+            lua.create_function(|_, mask: Mask| Ok(WildcardMask::from(&mask)))?,
+        )?;
+        let _ = lua.globals().set("WildcardMask", wildcard_table);
+        Ok(())
+    }
+}
+
 impl UserData for Network {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
-        fields.add_field_method_get("broadcast", |_, this| Ok(this.broadcast().to_owned()));
+        fields.add_field_method_get("broadcast", |_, this| Ok(this.try_broadcast().cloned()));
         fields.add_field_method_get("id", |_, this| Ok(this.netid().to_owned()));
         fields.add_field_method_get("mask", |_, this| Ok(this.mask().to_owned()));
     }
@@ -287,16 +494,64 @@ impl UserData for Network {
                 Err(_) => Ok(false),
             },
         );
+        methods.add_method("overlaps", |_, this, other: Network| {
+            Ok(this.overlaps(&other))
+        });
+        methods.add_method("is_subnet_of", |_, this, other: Network| {
+            Ok(this.is_subnet_of(&other))
+        });
+        methods.add_method("is_supernet_of", |_, this, other: Network| {
+            Ok(this.is_supernet_of(&other))
+        });
+        methods.add_method(
+            "exclude",
+            |_, this, other: Network| Ok(this.exclude(&other)),
+        );
+        methods.add_method("reverse_zones", |_, this, ()| Ok(this.reverse_zones()));
+        methods.add_method("subnets", |_, this, prefix: u8| {
+            Ok(lua_result(
+                this.subnets(prefix)
+                    .map(|subnets| subnets.collect::<Vec<Network>>()),
+            ))
+        });
+        methods.add_method("split", |_, this, count: u32| {
+            Ok(lua_result(this.split(count)))
+        });
+        methods.add_method("vlsm", |_, this, hosts: Vec<u128>| {
+            Ok(lua_result(this.vlsm(&hosts)))
+        });
+        methods.add_method("hosts", |lua, this, ()| {
+            let mut hosts = this.hosts();
+            lua.create_function_mut(move |_, ()| Ok(hosts.next()))
+        });
+        methods.add_method("addresses", |lua, this, ()| {
+            let mut addresses = this.addresses();
+            lua.create_function_mut(move |_, ()| Ok(addresses.next()))
+        });
+        methods.add_method("nth", |_, this, index: i128| Ok(this.nth(index)));
+        methods.add_method("first_host", |_, this, ()| Ok(this.first_host()));
+        methods.add_method("last_host", |_, this, ()| Ok(this.last_host()));
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| Ok(this.size()));
+        // Lua indexes start at 1 so net[1] is the net id and net[-1] is the last address
+        methods.add_meta_method(MetaMethod::Index, |_, this, key: mlua::Value| match key {
+            mlua::Value::Integer(index) if index > 0 => Ok(this.nth(index as i128 - 1)),
+            mlua::Value::Integer(index) if index < 0 => Ok(this.nth(index as i128)),
+            _ => Ok(None),
+        });
+        methods.add_meta_method(MetaMethod::Pairs, |lua, this, ()| {
+            let mut addresses = this.addresses().enumerate();
+            lua.create_function_mut(move |_, ()| match addresses.next() {
+                Some((i, address)) => Ok((Some(i + 1), Some(address))),
+                None => Ok((None, None)),
+            })
+        });
     }
 }
 impl LuaSetup for Network {
     fn setup(lua: &mlua::Lua) -> Result<()> {
         let constructor =
             lua.create_function(|_, (_, netid, mask): (mlua::Value, IpAddress, Mask)| {
-                match Network::new(netid.clone(), mask.clone()) {
-                    Ok(net) => Ok(Some(net)),
-                    Err(_) => Ok(None),
-                }
+                Ok(lua_result(Network::new(netid.clone(), mask.clone())))
             })?;
         let network_table = lua.create_table()?;
         let metatable = lua.create_table()?;
@@ -304,9 +559,22 @@ impl LuaSetup for Network {
         network_table.set_metatable(Some(metatable));
         network_table.set(
             "from",
-            lua.create_function(|_, net: String| match Network::from_str(net.as_str()) {
-                Ok(net) => Ok(Some(net)),
-                Err(_) => Ok(None),
+            lua.create_function(|_, net: String| Ok(lua_result(Network::from_str(net.as_str()))))?,
+        )?;
+        network_table.set(
+            "parse",
+            lua.create_function(|_, (net, strict): (String, Option<bool>)| {
+                Ok(lua_result(Network::parse(&net, strict.unwrap_or(true))))
+            })?,
+        )?;
+        network_table.set(
+            "summarize",
+            lua.create_function(|_, networks: Vec<Network>| Ok(Network::summarize(&networks)))?,
+        )?;
+        network_table.set(
+            "supernet",
+            lua.create_function(|_, networks: Vec<Network>| {
+                Ok(lua_result(Network::supernet(&networks)))
             })?,
         )?;
         let _ = lua.globals().set("Network", network_table);
@@ -314,14 +582,218 @@ impl LuaSetup for Network {
     }
 }
 
+impl UserData for IpRange {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("first", |_, this| Ok(this.first().to_owned()));
+        fields.add_field_method_get("last", |_, this| Ok(this.last().to_owned()));
+        fields.add_field_method_get("size", |_, this| Ok(this.size()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| Ok(this.size()));
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: IpRange| Ok(this == &other));
+        methods.add_method("contains", |_, this, addr: IpAddress| {
+            Ok(this.contains(&addr))
+        });
+        methods.add_method(
+            "contains_str",
+            |_, this, address: String| match IpAddress::from_str(&address) {
+                Ok(address) => Ok(this.contains(&address)),
+                Err(_) => Ok(false),
+            },
+        );
+        methods.add_method("addresses", |lua, this, ()| {
+            let mut addresses = this.addresses();
+            lua.create_function_mut(move |_, ()| Ok(addresses.next()))
+        });
+        methods.add_method("to_networks", |_, this, ()| Ok(this.to_networks()));
+    }
+}
+impl LuaSetup for IpRange {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let constructor = lua.create_function(
+            |_, (_, first, last): (mlua::Value, mlua::Value, Option<IpAddress>)| match first {
+                mlua::Value::String(range) => {
+                    Ok(lua_result(IpRange::from_str(&range.to_string_lossy())))
+                }
+                mlua::Value::UserData(first) => match (first.borrow::<IpAddress>(), last) {
+                    (Ok(first), Some(last)) => Ok(lua_result(IpRange::new(first.clone(), last))),
+                    _ => Ok((None, Some(Error::new(InvalidIpRange, "")))),
+                },
+                _ => Ok((None, Some(Error::new(InvalidIpRange, first.type_name())))),
+            },
+        )?;
+        let range_table = lua.create_table()?;
+        let metatable = lua.create_table()?;
+        metatable.set("__call", constructor)?;
+        range_table.set_metatable(Some(metatable));
+        range_table.set(
+            "from_network",
+            lua.create_function(|_, net: Network| Ok(IpRange::from_network(&net)))?,
+        )?;
+        let _ = lua.globals().set("IpRange", range_table);
+        Ok(())
+    }
+}
+
+/// Converts a Lua Network, IpAddress or a string of one of them into a network
+fn network_from_lua(value: &mlua::Value) -> Option<Network> {
+    match value {
+        mlua::Value::UserData(data) => match data.borrow::<Network>() {
+            Ok(network) => Some(network.clone()),
+            Err(_) => data
+                .borrow::<IpAddress>()
+                .ok()
+                .map(|address| Network::from(&*address)),
+        },
+        mlua::Value::String(value) => {
+            let value = value.to_string_lossy();
+            match Network::from_str(&value) {
+                Ok(network) => Some(network),
+                Err(_) => IpAddress::from_str(&value)
+                    .ok()
+                    .map(|address| Network::from(&address)),
+            }
+        }
+        _ => None,
+    }
+}
+
+impl UserData for IpSet<mlua::Value> {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| Ok(this.len()));
+        methods.add_method_mut(
+            "insert",
+            |_, this, (network, value): (mlua::Value, Option<mlua::Value>)| match network_from_lua(
+                &network,
+            ) {
+                Some(network) => {
+                    this.insert(&network, value.unwrap_or(mlua::Value::Boolean(true)));
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+        );
+        methods.add_method_mut(
+            "remove",
+            |_, this, network: mlua::Value| match network_from_lua(&network) {
+                Some(network) => {
+                    this.remove(&network);
+                    Ok(true)
+                }
+                None => Ok(false),
+            },
+        );
+        methods.add_method("get", |_, this, network: Network| {
+            Ok(this.get(&network).cloned())
+        });
+        methods.add_method("contains", |_, this, addr: IpAddress| {
+            Ok(this.contains(&addr))
+        });
+        methods.add_method(
+            "contains_str",
+            |_, this, address: String| match IpAddress::from_str(&address) {
+                Ok(address) => Ok(this.contains(&address)),
+                Err(_) => Ok(false),
+            },
+        );
+        methods.add_method("longest_match", |_, this, addr: IpAddress| {
+            match this.longest_match(&addr) {
+                Some((network, value)) => Ok((Some(value.clone()), Some(network))),
+                None => Ok((None, None)),
+            }
+        });
+        methods.add_method("union", |_, this, other: mlua::UserDataRef<Self>| {
+            Ok(this.union(&other))
+        });
+        methods.add_method("intersection", |_, this, other: mlua::UserDataRef<Self>| {
+            Ok(this.intersection(&*other))
+        });
+        methods.add_method("difference", |_, this, other: mlua::UserDataRef<Self>| {
+            Ok(this.difference(&*other))
+        });
+        methods.add_method("networks", |_, this, ()| Ok(this.networks()));
+        methods.add_meta_method(MetaMethod::Pairs, |lua, this, ()| {
+            let mut entries = this
+                .entries()
+                .into_iter()
+                .map(|(network, value)| (network, value.clone()))
+                .collect::<Vec<(Network, mlua::Value)>>()
+                .into_iter();
+            lua.create_function_mut(move |_, ()| match entries.next() {
+                Some((network, value)) => Ok((Some(network), Some(value))),
+                None => Ok((None, None)),
+            })
+        });
+    }
+}
+impl LuaSetup for IpSet {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let constructor = lua.create_function(
+            |_, (_, networks): (mlua::Value, Option<Vec<mlua::Value>>)| {
+                let mut set = IpSet::new();
+                for network in networks.unwrap_or_default() {
+                    match network_from_lua(&network) {
+                        Some(network) => {
+                            set.insert(&network, mlua::Value::Boolean(true));
+                        }
+                        None => {
+                            let input = network.to_string().unwrap_or_default();
+                            return Ok((None, Some(Error::new(InvalidNetwork, input))));
+                        }
+                    }
+                }
+                Ok((Some(set), None))
+            },
+        )?;
+        let set_table = lua.create_table()?;
+        let metatable = lua.create_table()?;
+        metatable.set("__call", constructor)?;
+        set_table.set_metatable(Some(metatable));
+        let _ = lua.globals().set("IpSet", set_table);
+        Ok(())
+    }
+}
+
+impl UserData for Oui {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("prefix", |_, this| Ok(this.prefix()));
+        fields.add_field_method_get("length", |_, this| Ok(this.length()));
+        fields.add_field_method_get("vendor", |_, this| Ok(this.vendor().to_owned()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_method("contains", |lua, this, address: mlua::Value| {
+            let address = match address {
+                mlua::Value::String(address) => {
+                    match MacAddress::from_str(&address.to_string_lossy()) {
+                        Ok(address) => address,
+                        Err(_) => return Ok(false),
+                    }
+                }
+                address => MacAddress::from_lua(address, lua)?,
+            };
+            Ok(this.contains(&address))
+        });
+    }
+}
+
 impl UserData for MacAddress {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("address", |_, this| Ok(this.address()));
-        fields.add_field_method_get("vendor", |_, this| Ok(this.vendor().clone()));
+        fields.add_field_method_get("vendor", |_, this| Ok(this.vendor()));
     }
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
         methods.add_method("as_bytes", |_, this, ()| Ok(this.as_bytes().to_owned()));
+        methods.add_method("format", |_, this, notation: Option<MacNotation>| {
+            Ok(this.format(&notation.unwrap_or(MacNotation::Colon)))
+        });
+        methods.add_method("is_multicast", |_, this, ()| Ok(this.is_multicast()));
+        methods.add_method("is_unicast", |_, this, ()| Ok(this.is_unicast()));
+        methods.add_method("is_local", |_, this, ()| Ok(this.is_local()));
+        methods.add_method("is_universal", |_, this, ()| Ok(this.is_universal()));
+        methods.add_method("is_broadcast", |_, this, ()| Ok(this.is_broadcast()));
         methods.add_meta_method(MetaMethod::Eq, |_, this, other: MacAddress| {
             Ok(this == &other)
         });
@@ -335,13 +807,9 @@ impl UserData for MacAddress {
 }
 impl LuaSetup for MacAddress {
     fn setup(lua: &mlua::Lua) -> Result<()> {
-        let constructor =
-            lua.create_function(
-                |_, (_, address): (mlua::Value, String)| match MacAddress::from_str(&address) {
-                    Ok(address) => Ok(Some(address)),
-                    Err(_) => Ok(None),
-                },
-            )?;
+        let constructor = lua.create_function(|_, (_, address): (mlua::Value, String)| {
+            Ok(lua_result(MacAddress::from_str(&address)))
+        })?;
         let macaddress_table = lua.create_table()?;
         let metatable_table = lua.create_table()?;
         metatable_table.set("__call", constructor)?;
@@ -350,11 +818,85 @@ impl LuaSetup for MacAddress {
             "is_valid",
             lua.create_function(|_, address: String| Ok(MacAddress::is_valid(&address)))?,
         )?;
+        macaddress_table.set(
+            "parse_all",
+            lua.create_function(|lua, addresses: Vec<String>| {
+                MacAddress::parse_all(&addresses)
+                    .into_iter()
+                    .map(|address| match address {
+                        Ok(address) => address.into_lua(lua),
+                        Err(e) => e.into_lua(lua),
+                    })
+                    .collect::<Result<Vec<mlua::Value>>>()
+            })?,
+        )?;
+        macaddress_table.set(
+            "ouis_for_vendor",
+            lua.create_function(|_, pattern: String| Ok(MacAddress::ouis_for_vendor(&pattern)))?,
+        )?;
+        macaddress_table.set(
+            "load_vendor_database",
+            lua.create_function(
+                |_, path: String| match MacAddress::load_vendor_database(path) {
+                    Ok(()) => Ok((true, None)),
+                    Err(e) => Ok((false, Some(e))),
+                },
+            )?,
+        )?;
+        macaddress_table.set(
+            "random",
+            lua.create_function(|_, oui: mlua::Value| {
+                let invalid = |oui: &mlua::Value| {
+                    let input = oui.to_string().unwrap_or_default();
+                    Ok((None, Some(Error::new(InvalidMacAddress, input))))
+                };
+                let oui = match &oui {
+                    mlua::Value::Nil => None,
+                    mlua::Value::String(value) => {
+                        let value = value.to_string_lossy().replace([':', '-', '.'], "");
+                        match u32::from_str_radix(&value, 16) {
+                            Ok(prefix) if value.len() == 6 && !value.starts_with('+') => {
+                                Some(prefix.to_be_bytes()[1..].try_into().unwrap())
+                            }
+                            _ => return invalid(&oui),
+                        }
+                    }
+                    mlua::Value::Table(value) => {
+                        match <[u8; 3]>::try_from(
+                            value.sequence_values::<u8>().flatten().collect::<Vec<u8>>(),
+                        ) {
+                            Ok(prefix) => Some(prefix),
+                            Err(_) => return invalid(&oui),
+                        }
+                    }
+                    _ => return invalid(&oui),
+                };
+                Ok(lua_result(MacAddress::random(oui)))
+            })?,
+        )?;
         let _ = lua.globals().set("MacAddress", macaddress_table);
         Ok(())
     }
 }
 
+impl UserData for MacNotation {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+    }
+}
+impl LuaSetup for MacNotation {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let notations_table = lua.create_table()?;
+        notations_table.set("colon", MacNotation::Colon)?;
+        notations_table.set("dash", MacNotation::Dash)?;
+        notations_table.set("bare", MacNotation::Bare)?;
+        notations_table.set("cisco", MacNotation::Cisco)?;
+        notations_table.set("eui64", MacNotation::Eui64)?;
+        let _ = lua.globals().set("MacNotation", notations_table);
+        Ok(())
+    }
+}
+
 impl UserData for Interface {
     fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
         fields.add_field_method_get("name", |_, this| Ok(this.name().to_owned()));
@@ -364,6 +906,15 @@ impl UserData for Interface {
         fields.add_field_method_get("ipv4", |_, this| Ok(this.ipv4().to_owned()));
         fields.add_field_method_get("ipv6", |_, this| Ok(this.ipv6().to_owned()));
         fields.add_field_method_get("mask", |_, this| Ok(this.mask().to_owned()));
+        fields.add_field_method_get("addresses", |_, this| Ok(this.addresses().to_owned()));
+        fields.add_field_method_get("mtu", |_, this| Ok(this.mtu().to_owned()));
+        fields.add_field_method_get("state", |_, this| Ok(this.state().to_owned()));
+        fields.add_field_method_get("up", |_, this| Ok(this.is_up()));
+        fields.add_field_method_get("loopback", |_, this| Ok(this.is_loopback()));
+        fields.add_field_method_get("point_to_point", |_, this| Ok(this.is_point_to_point()));
+        fields.add_field_method_get("multicast", |_, this| Ok(this.is_multicast()));
+        fields.add_field_method_get("broadcast", |_, this| Ok(this.is_broadcast()));
+        fields.add_field_method_get("counters", |_, this| Ok(this.counters().to_owned()));
     }
     fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
         methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
@@ -374,23 +925,399 @@ impl LuaSetup for Interface {
         let interface_table = lua.create_table()?;
         interface_table.set(
             "by_index",
-            lua.create_function(|_, index: u32| match Interface::by_index(index) {
-                Ok(inf) => Ok(Some(inf)),
-                Err(_) => Ok(None),
-            })?,
+            lua.create_function(|_, index: u32| Ok(lua_result(Interface::by_index(index))))?,
         )?;
         interface_table.set(
             "by_name",
-            lua.create_function(|_, name: String| match Interface::by_name(&name) {
-                Ok(inf) => Ok(Some(inf)),
-                Err(_) => Ok(None),
-            })?,
+            lua.create_function(|_, name: String| Ok(lua_result(Interface::by_name(&name))))?,
         )?;
         interface_table.set("all", lua.create_function(|_, ()| Ok(Interface::all()))?)?;
+        #[cfg(target_os = "linux")]
+        interface_table.set(
+            "events",
+            lua.create_function(|lua, timeout: Option<f64>| {
+                let mut watcher = match InterfaceWatcher::new() {
+                    Ok(watcher) => watcher,
+                    Err(e) => return Ok((None, Some(e))),
+                };
+                let timeout = timeout.map(std::time::Duration::from_secs_f64);
+                Ok((
+                    Some(lua.create_function_mut(move |_, ()| {
+                        Ok(match watcher.next_event(timeout) {
+                            Ok(event) => (event, None),
+                            Err(e) => (None, Some(e)),
+                        })
+                    })?),
+                    None,
+                ))
+            })?,
+        )?;
         let _ = lua.globals().set("Interface", interface_table);
         Ok(())
     }
 }
+impl UserData for Route {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("destination", |_, this| Ok(this.destination().to_owned()));
+        fields.add_field_method_get("gateway", |_, this| Ok(this.gateway().to_owned()));
+        fields.add_field_method_get("interface", |_, this| Ok(this.interface().ok()));
+        fields.add_field_method_get("interface_name", |_, this| {
+            Ok(this.interface_name().to_owned())
+        });
+        fields.add_field_method_get("metric", |_, this| Ok(this.metric()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_method("is_default", |_, this, ()| Ok(this.is_default()));
+    }
+}
+impl LuaSetup for Route {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let route_table = lua.create_table()?;
+        route_table.set("all", lua.create_function(|_, ()| Ok(Route::all()))?)?;
+        route_table.set(
+            "route_for",
+            lua.create_function(|lua, address: mlua::Value| {
+                let address = match address {
+                    mlua::Value::String(address) => {
+                        match IpAddress::from_str(&address.to_string_lossy()) {
+                            Ok(address) => address,
+                            Err(e) => return Ok((None, Some(e))),
+                        }
+                    }
+                    address => IpAddress::from_lua(address, lua)?,
+                };
+                Ok((Route::route_for(&address), None))
+            })?,
+        )?;
+        let _ = lua.globals().set("Route", route_table);
+        Ok(())
+    }
+}
+
+impl LuaSetup for Neighbor {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let neighbor_table = lua.create_table()?;
+        neighbor_table.set(
+            "all",
+            lua.create_function(|lua, ()| {
+                Neighbor::all()
+                    .into_iter()
+                    .map(|neighbor| {
+                        let entry = lua.create_table()?;
+                        entry.set("address", neighbor.address().to_owned())?;
+                        entry.set("mac", neighbor.mac().to_owned())?;
+                        entry.set("vendor", neighbor.vendor().to_owned())?;
+                        entry.set("interface", neighbor.interface_name().to_owned())?;
+                        entry.set("state", neighbor.state().to_string())?;
+                        Ok(entry)
+                    })
+                    .collect::<Result<Vec<mlua::Table>>>()
+            })?,
+        )?;
+        let _ = lua.globals().set("Neighbor", neighbor_table);
+        Ok(())
+    }
+}
+
+impl UserData for Service {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("name", |_, this| Ok(this.name().to_owned()));
+        fields.add_field_method_get("port", |_, this| Ok(this.port()));
+        fields.add_field_method_get("protocol", |_, this| Ok(this.protocol().to_string()));
+        fields.add_field_method_get("aliases", |_, this| Ok(this.aliases().to_owned()));
+        fields.add_field_method_get("description", |_, this| Ok(this.description().to_owned()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+    }
+}
+impl LuaSetup for Service {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let service_table = lua.create_table()?;
+        service_table.set(
+            "by_name",
+            lua.create_function(|_, (name, protocol): (String, Option<String>)| {
+                match protocol.map(|protocol| Protocol::from_str(&protocol)) {
+                    Some(Err(e)) => Ok((None, Some(e))),
+                    Some(Ok(protocol)) => Ok((Service::by_name(&name, Some(protocol)), None)),
+                    None => Ok((Service::by_name(&name, None), None)),
+                }
+            })?,
+        )?;
+        service_table.set(
+            "by_port",
+            lua.create_function(|_, (port, protocol): (u16, Option<String>)| {
+                match protocol.map(|protocol| Protocol::from_str(&protocol)) {
+                    Some(Err(e)) => Ok((None, Some(e))),
+                    Some(Ok(protocol)) => Ok((Service::by_port(port, Some(protocol)), None)),
+                    None => Ok((Service::by_port(port, None), None)),
+                }
+            })?,
+        )?;
+        service_table.set("all", lua.create_function(|_, ()| Ok(Service::all()))?)?;
+        service_table.set(
+            "load_services_file",
+            lua.create_function(|_, path: String| match Service::load_services_file(path) {
+                Ok(()) => Ok((true, None)),
+                Err(e) => Ok((false, Some(e))),
+            })?,
+        )?;
+        let _ = lua.globals().set("Service", service_table);
+        Ok(())
+    }
+}
+
+impl UserData for PortRange {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_method(MetaMethod::Len, |_, this, ()| Ok(this.len()));
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: PortRange| {
+            Ok(this == &other)
+        });
+        methods.add_method("contains", |_, this, port: u16| Ok(this.contains(port)));
+        methods.add_method("ports", |lua, this, ()| {
+            let mut ports = this.ports().collect::<Vec<u16>>().into_iter();
+            lua.create_function_mut(move |_, ()| Ok(ports.next()))
+        });
+        methods.add_method("ranges", |_, this, ()| {
+            Ok(this
+                .ranges()
+                .iter()
+                .map(|(first, last)| vec![*first, *last])
+                .collect::<Vec<Vec<u16>>>())
+        });
+    }
+}
+impl LuaSetup for PortRange {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let constructor = lua.create_function(|_, (_, range): (mlua::Value, String)| {
+            Ok(lua_result(PortRange::from_str(&range)))
+        })?;
+        let range_table = lua.create_table()?;
+        let metatable = lua.create_table()?;
+        metatable.set("__call", constructor)?;
+        range_table.set_metatable(Some(metatable));
+        let _ = lua.globals().set("PortRange", range_table);
+        Ok(())
+    }
+}
+
+/// Looks up a port of the `port` table that has no constant by its service name, the
+/// underscores of the key stand for dashes
+pub fn port_by_name(_: &mlua::Lua, (_, name): (mlua::Value, String)) -> Result<Option<u16>> {
+    Ok(parse_port(&name.replace('_', "-")).ok())
+}
+
+impl UserData for Record {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("name", |_, this| Ok(this.name().to_owned()));
+        fields.add_field_method_get("type", |_, this| Ok(this.kind_name()));
+        fields.add_field_method_get("ttl", |_, this| Ok(this.ttl()));
+        fields.add_field_method_get("value", |_, this| Ok(this.data().to_string()));
+        fields.add_field_method_get("data", |lua, this| {
+            let data = lua.create_table()?;
+            match this.data() {
+                RecordData::A(address) | RecordData::Aaaa(address) => {
+                    data.set("address", address.to_owned())?
+                }
+                RecordData::Ptr(name) | RecordData::Ns(name) | RecordData::Cname(name) => {
+                    data.set("target", name.as_str())?
+                }
+                RecordData::Mx {
+                    preference,
+                    exchange,
+                } => {
+                    data.set("preference", *preference)?;
+                    data.set("exchange", exchange.as_str())?;
+                }
+                RecordData::Txt(strings) => data.set("text", strings.to_owned())?,
+                RecordData::Srv {
+                    priority,
+                    weight,
+                    port,
+                    target,
+                } => {
+                    data.set("priority", *priority)?;
+                    data.set("weight", *weight)?;
+                    data.set("port", *port)?;
+                    data.set("target", target.as_str())?;
+                }
+                RecordData::Soa {
+                    mname,
+                    rname,
+                    serial,
+                    refresh,
+                    retry,
+                    expire,
+                    minimum,
+                } => {
+                    data.set("mname", mname.as_str())?;
+                    data.set("rname", rname.as_str())?;
+                    data.set("serial", *serial)?;
+                    data.set("refresh", *refresh)?;
+                    data.set("retry", *retry)?;
+                    data.set("expire", *expire)?;
+                    data.set("minimum", *minimum)?;
+                }
+                RecordData::Other { data: raw, .. } => data.set("raw", lua.create_string(raw)?)?,
+            }
+            Ok(data)
+        });
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+    }
+}
+
+impl UserData for Response {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("id", |_, this| Ok(this.id()));
+        fields.add_field_method_get("status", |_, this| Ok(this.status()));
+        fields.add_field_method_get("code", |_, this| Ok(this.code()));
+        fields.add_field_method_get("authoritative", |_, this| Ok(this.is_authoritative()));
+        fields.add_field_method_get("truncated", |_, this| Ok(this.is_truncated()));
+        fields.add_field_method_get("recursion_available", |_, this| {
+            Ok(this.is_recursion_available())
+        });
+        fields.add_field_method_get("transport", |_, this| Ok(this.transport().to_string()));
+        fields.add_field_method_get("answers", |_, this| Ok(this.answers().to_owned()));
+        fields.add_field_method_get("authorities", |_, this| Ok(this.authorities().to_owned()));
+        fields.add_field_method_get("additionals", |_, this| Ok(this.additionals().to_owned()));
+        fields.add_field_method_get("addresses", |_, this| Ok(this.addresses()));
+        fields.add_field_method_get("raw", |lua, this| lua.create_string(this.raw()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+    }
+}
+
+/// Creates the `dns` table of the `yrnu` global
+pub fn dns_table(lua: &mlua::Lua) -> Result<mlua::Table> {
+    let dns_table = lua.create_table()?;
+    dns_table.set(
+        "query",
+        lua.create_function(
+            |_, (name, kind, options): (String, Option<String>, Option<mlua::Table>)| {
+                Ok(lua_result(dns_query(&name, kind, options)))
+            },
+        )?,
+    )?;
+    Ok(dns_table)
+}
+
+/// Sends a `yrnu.dns.query` query, the system resolver is used when no server is giving
+fn dns_query(
+    name: &str,
+    kind: Option<String>,
+    options: Option<mlua::Table>,
+) -> std::result::Result<Response, Error> {
+    let kind = match kind {
+        Some(kind) => RecordType::from_str(&kind)?,
+        None => RecordType::A,
+    };
+    let options = match options {
+        Some(options) => options,
+        None => return Resolver::system()?.query(name, kind),
+    };
+    let mut resolver = match options.get::<Option<String>>("server") {
+        Ok(Some(server)) => Resolver::new(&IpAddress::from_str(&server)?),
+        Ok(None) => Resolver::system()?,
+        Err(_) => Resolver::new(&options.get::<IpAddress>("server")?),
+    };
+    if let Some(port) = options.get::<Option<u16>>("port")? {
+        resolver = resolver.with_port(port);
+    }
+    if let Some(transport) = options.get::<Option<String>>("transport")? {
+        resolver = resolver.with_transport(Transport::from_str(&transport)?);
+    }
+    if let Some(timeout) = options.get::<Option<f64>>("timeout")? {
+        resolver = resolver.with_timeout(
+            std::time::Duration::try_from_secs_f64(timeout)
+                .map_err(|e| Error::new(DnsQueryFailed, timeout).with_source(e))?,
+        );
+    }
+    resolver.query(name, kind)
+}
+
+impl UserData for InterfaceEvent {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("kind", |_, this| Ok(this.kind().to_owned()));
+        fields.add_field_method_get("interface", |_, this| Ok(this.interface().to_owned()));
+        fields.add_field_method_get("address", |_, this| Ok(this.address().cloned()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+    }
+}
+
+impl UserData for InterfaceAddress {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("address", |_, this| Ok(this.address().to_owned()));
+        fields.add_field_method_get("mask", |_, this| Ok(this.mask().to_owned()));
+        fields.add_field_method_get("network", |_, this| Ok(this.network()));
+    }
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: InterfaceAddress| {
+            Ok(this == &other)
+        });
+    }
+}
+impl LuaSetup for InterfaceAddress {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let constructor = lua.create_function(|_, (_, address): (mlua::Value, String)| {
+            Ok(lua_result(InterfaceAddress::from_str(&address)))
+        })?;
+        let address_table = lua.create_table()?;
+        let metatable = lua.create_table()?;
+        metatable.set("__call", constructor)?;
+        address_table.set_metatable(Some(metatable));
+        address_table.set(
+            "is_valid",
+            lua.create_function(|_, address: String| Ok(InterfaceAddress::is_valid(&address)))?,
+        )?;
+        let _ = lua.globals().set("InterfaceAddress", address_table);
+        Ok(())
+    }
+}
+
+impl UserData for InterfaceState {
+    fn add_methods<M: UserDataMethods<Self>>(methods: &mut M) {
+        methods.add_meta_method(MetaMethod::ToString, |_, this, ()| Ok(format!("{}", this)));
+        methods.add_meta_method(MetaMethod::Eq, |_, this, other: InterfaceState| {
+            Ok(this == &other)
+        });
+    }
+}
+impl LuaSetup for InterfaceState {
+    fn setup(lua: &mlua::Lua) -> Result<()> {
+        let states_table = lua.create_table()?;
+        states_table.set("up", InterfaceState::Up)?;
+        states_table.set("down", InterfaceState::Down)?;
+        states_table.set("dormant", InterfaceState::Dormant)?;
+        states_table.set("lowerlayerdown", InterfaceState::LowerLayerDown)?;
+        states_table.set("notpresent", InterfaceState::NotPresent)?;
+        states_table.set("testing", InterfaceState::Testing)?;
+        states_table.set("unknown", InterfaceState::Unknown)?;
+        let _ = lua.globals().set("InterfaceState", states_table);
+        Ok(())
+    }
+}
+
+impl UserData for InterfaceCounters {
+    fn add_fields<F: mlua::UserDataFields<Self>>(fields: &mut F) {
+        fields.add_field_method_get("rx_bytes", |_, this| Ok(this.rx_bytes()));
+        fields.add_field_method_get("rx_packets", |_, this| Ok(this.rx_packets()));
+        fields.add_field_method_get("rx_errors", |_, this| Ok(this.rx_errors()));
+        fields.add_field_method_get("rx_dropped", |_, this| Ok(this.rx_dropped()));
+        fields.add_field_method_get("tx_bytes", |_, this| Ok(this.tx_bytes()));
+        fields.add_field_method_get("tx_packets", |_, this| Ok(this.tx_packets()));
+        fields.add_field_method_get("tx_errors", |_, this| Ok(this.tx_errors()));
+        fields.add_field_method_get("tx_dropped", |_, this| Ok(this.tx_dropped()));
+    }
+}
+
 // Helper function to write to a file
 fn append(
     path: &PathBuf,
@@ -2,7 +2,7 @@ use clap::builder;
 use clap::{Arg, ArgAction, ArgGroup, ArgMatches, Command, command, value_parser};
 use git2::FetchOptions;
 use log::{LevelFilter, error, info, warn};
-use mlua::Lua;
+use mlua::{FromLua, IntoLua, Lua};
 use quick_xml::Reader;
 use quick_xml::events::{BytesDecl, BytesText, Event};
 use quick_xml::writer::Writer;
@@ -15,10 +15,14 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use which::which;
 use yrnu::config::{self, SSHAuthType, connect};
-use yrnu::core::{Interface, IpAddress, MacAddress, Mask, Network, Path, Url};
+use yrnu::core::{
+    Interface, InterfaceAddress, IpAddress, IpRange, MacAddress, Mask, Network, Path, Url,
+};
 use yrnu::lua;
 use yrnu::lua::interpreter;
 use yrnu::parser::*;
+use yrnu::port::{self, PortRange, Service};
+use yrnu::{Error, ErrorKind};
 
 /// The global yrnu
 #[derive(Debug, Clone, Default)]
@@ -37,6 +41,13 @@ struct Yrnu {
     install_cmd: String,
 }
 
+// The input of a plugin argument the wizard asks for
+struct ArgPrompt<'a> {
+    input: &'a mut String,
+    prompt: &'a str,
+    required: bool,
+}
+
 impl Yrnu {
     // Handle the creation of global argument Lua function
     fn get_arg_lua_function(
@@ -182,6 +193,12 @@ impl Yrnu {
                                 Ok(())
                             },
                         )?,
+                        "port" => self.arg_setter::<u16>(arg_name, update)?,
+                        "port-range" => self.arg_setter::<PortRange>(arg_name, update)?,
+                        "ip-range" => self.arg_setter::<IpRange>(arg_name, update)?,
+                        "interface-address" => {
+                            self.arg_setter::<InterfaceAddress>(arg_name, update)?
+                        }
                         "mask" => self.lua.create_function(
                             move |_, (this, value): (mlua::Table, Mask)| {
                                 if let Ok(update) = &update {
@@ -277,6 +294,25 @@ impl Yrnu {
             };
         Ok(func)
     }
+    // Creates the setter of a plugin argument of type T, the value is passed to the update
+    // function of the argument or set in the table when there is none
+    fn arg_setter<T: FromLua + IntoLua + 'static>(
+        &self,
+        arg_name: String,
+        update: mlua::Result<mlua::Function>,
+    ) -> mlua::Result<mlua::Function> {
+        self.lua
+            .create_function(move |_, (this, value): (mlua::Table, T)| {
+                if let Ok(update) = &update {
+                    if let Err(e) = update.call::<()>((this, value)) {
+                        println!("{e}");
+                    }
+                } else {
+                    this.set(arg_name.to_owned(), value)?;
+                }
+                Ok(())
+            })
+    }
     // Handle the plugin argument CLI creation
     fn get_arg_clap_cmd(
         &self,
@@ -362,7 +398,11 @@ impl Yrnu {
                         .value_parser(value_parser!(f64)),
                     "boolish" => arg.value_parser(builder::BoolishValueParser::new()),
                     "ip-address" => arg.value_parser(IpAddress::from_str),
-                    "network" => arg.value_parser(Network::from_str),
+                    "network" => arg.value_parser(|s: &str| Network::parse(s, true)),
+                    "port" => arg.value_parser(port::parse_port),
+                    "port-range" => arg.value_parser(PortRange::from_str),
+                    "ip-range" => arg.value_parser(IpRange::from_str),
+                    "interface-address" => arg.value_parser(InterfaceAddress::from_str),
                     "mask" => arg.value_parser(Mask::from_str),
                     "mac-address" => arg.value_parser(MacAddress::from_str),
                     "interface" => arg.value_parser(Interface::from_str),
@@ -1282,7 +1322,7 @@ Key features include configuring network settings, sending custom traffic, and d
         Ok(self)
     }
     // Initiate a new Yrnu instance
-    pub fn new(level: Option<log::LevelFilter>) -> Result<Self, Box<dyn std::error::Error>> {
+    pub fn new(level: Option<log::LevelFilter>) -> Result<Self, Error> {
         let debug = std::env::var("YRNU_DEBUG") == Ok(String::from("1"));
         let mut path = match std::env::var("YRNU_CONFIG_DIR") {
             Ok(path) => PathBuf::from(path),
@@ -1322,6 +1362,22 @@ Key features include configuring network settings, sending custom traffic, and d
                     .unwrap_or_else(|e| error!("{e}"));
             }
         };
+        // Vendor databases in the config dir override the built-in one
+        for file in ["manuf", "oui.csv"] {
+            let file = path.join(file);
+            if file.is_file() {
+                if let Err(e) = MacAddress::load_vendor_database(&file) {
+                    warn!("Failed to load the vendor database {}: {e}", file.display());
+                }
+            }
+        }
+        // Services in the config dir are added to the curated ones
+        let file = path.join("services");
+        if file.is_file() {
+            if let Err(e) = Service::load_services_file(&file) {
+                warn!("Failed to load the services file {}: {e}", file.display());
+            }
+        }
         let args = Self::define_cli_usage();
         let mut yrnu = Self {
             lua: lua.unwrap(),
@@ -1329,10 +1385,60 @@ Key features include configuring network settings, sending custom traffic, and d
             args: args.clone(),
             ..Default::default()
         };
-        yrnu = yrnu.load_plugins()?;
-        let yrnu = yrnu.lua_setup()?;
+        let root = yrnu.root.display().to_string();
+        let load_failed =
+            |e: mlua::Error| Error::new(ErrorKind::PluginLoadFailed, &root).with_source(e);
+        yrnu = yrnu.load_plugins().map_err(load_failed)?;
+        let yrnu = yrnu.lua_setup().map_err(load_failed)?;
         Ok(yrnu)
     }
+    // Passes the value of a plugin argument of type T to its update function, the value is
+    // taken from the matches or, in wizard mode, parsed from the input and asked for again
+    // while it is invalid and the argument is required
+    fn update_arg<T, E>(
+        update: &mlua::Function,
+        config_table: &mlua::Table,
+        arg_matches: &ArgMatches,
+        arg_name: &str,
+        arg_action: &str,
+        wizard: Option<ArgPrompt>,
+        parse: impl Fn(&str) -> Result<T, E>,
+    ) where
+        T: IntoLua + Clone + Send + Sync + 'static,
+    {
+        if arg_action == "store-table" {
+            if let Some(vals) = arg_matches.get_many::<T>(arg_name) {
+                _ = update.call::<()>((config_table.clone(), vals.cloned().collect::<Vec<T>>()))
+            }
+            return;
+        }
+        let value = match wizard {
+            Some(ArgPrompt {
+                input,
+                prompt,
+                required,
+            }) => {
+                let mut value = parse(input.trim());
+                while required && value.is_err() {
+                    input.clear();
+                    print!("{prompt}: ");
+                    std::io::stdout().flush().unwrap_or_else(|e| {
+                        error!("Something went bad!\nError: {e}");
+                    });
+                    std::io::stdin().read_line(input).unwrap_or_else(|e| {
+                        error!("Something went bad!\nError: {e}");
+                        1
+                    });
+                    value = parse(input.trim());
+                }
+                value.ok()
+            }
+            None => arg_matches.get_one::<T>(arg_name).cloned(),
+        };
+        if let Some(value) = value {
+            _ = update.call::<()>((config_table.clone(), value))
+        }
+    }
     /// Handle the cli usage of the plugins
     pub fn handle_cli_matches(
         &self,
@@ -1460,7 +1566,11 @@ Key features include configuring network settings, sending custom traffic, and d
                             "uint" => input.parse::<u64>().is_ok(),
                             "real" => input.parse::<f64>().is_ok(),
                             "ip-address" => IpAddress::is_valid(&input),
-                            "network" => Network::from_str(&input).is_ok(),
+                            "network" => Network::parse(&input, true).is_ok(),
+                            "port" => port::parse_port(&input).is_ok(),
+                            "port-range" => PortRange::from_str(&input).is_ok(),
+                            "ip-range" => IpRange::from_str(&input).is_ok(),
+                            "interface-address" => InterfaceAddress::is_valid(&input),
                             "mask" => IpAddress::from_str(&input).is_ok(),
                             "mac-address" => MacAddress::is_valid(&input),
                             "interface" => Interface::from_str(&input).is_ok(),
@@ -1803,7 +1913,7 @@ Key features include configuring network settings, sending custom traffic, and d
                             }
                         } else {
                             let value = if wizard {
-                                let mut num = input.trim().parse::<Network>();
+                                let mut num = Network::parse(input.trim(), true);
                                 if required {
                                     while num.is_err() {
                                         input.clear();
@@ -1817,7 +1927,7 @@ Key features include configuring network settings, sending custom traffic, and d
                                                 1
                                             },
                                         );
-                                        num = input.trim().parse::<Network>();
+                                        num = Network::parse(input.trim(), true);
                                     }
                                 }
                                 if num.is_ok() {
@@ -1840,6 +1950,58 @@ Key features include configuring network settings, sending custom traffic, and d
                             }
                         }
                     }
+                    "port" => Self::update_arg(
+                        &update,
+                        &config_table,
+                        arg_matches,
+                        &arg_name,
+                        &arg_action,
+                        wizard.then(|| ArgPrompt {
+                            input: &mut input,
+                            prompt: &prompt,
+                            required,
+                        }),
+                        port::parse_port,
+                    ),
+                    "port-range" => Self::update_arg(
+                        &update,
+                        &config_table,
+                        arg_matches,
+                        &arg_name,
+                        &arg_action,
+                        wizard.then(|| ArgPrompt {
+                            input: &mut input,
+                            prompt: &prompt,
+                            required,
+                        }),
+                        PortRange::from_str,
+                    ),
+                    "ip-range" => Self::update_arg(
+                        &update,
+                        &config_table,
+                        arg_matches,
+                        &arg_name,
+                        &arg_action,
+                        wizard.then(|| ArgPrompt {
+                            input: &mut input,
+                            prompt: &prompt,
+                            required,
+                        }),
+                        IpRange::from_str,
+                    ),
+                    "interface-address" => Self::update_arg(
+                        &update,
+                        &config_table,
+                        arg_matches,
+                        &arg_name,
+                        &arg_action,
+                        wizard.then(|| ArgPrompt {
+                            input: &mut input,
+                            prompt: &prompt,
+                            required,
+                        }),
+                        InterfaceAddress::from_str,
+                    ),
                     "mask" => {
                         if arg_action == "store-table" {
                             if let Some(vals) = arg_matches.get_many::<Mask>(&arg_name) {
@@ -2283,11 +2445,13 @@ Key features include configuring network settings, sending custom traffic, and d
             .to_owned()
     }
     /// Runs a Lua script
-    pub fn run_script(&self, name: &String) -> mlua::Result<()> {
-        let mut file = File::open(name)?;
+    pub fn run_script(&self, name: &String) -> Result<(), Error> {
         let mut content = String::new();
-        file.read_to_string(&mut content)?;
-        lua::run(&self.lua, &content)?;
+        File::open(name)
+            .and_then(|mut file| file.read_to_string(&mut content))
+            .map_err(|e| Error::new(ErrorKind::ScriptFailed, name).with_source(e))?;
+        lua::run(&self.lua, &content)
+            .map_err(|e| Error::new(ErrorKind::ScriptFailed, name).with_source(e))?;
         Ok(())
     }
     /// Prints all the plugins that currently installed on the system
@@ -2496,26 +2660,24 @@ Key features include configuring network settings, sending custom traffic, and d
                                     metatable.set("__tostring", tostring.clone())?;
                                     output_table.set_metatable(Some(metatable));
                                     output_table.set("success", false)?;
-                                    output_table.set("output", e.message())?;
+                                    output_table.set("output", e.to_string())?;
+                                    output_table.set("error", e)?;
                                     output_tables.push(output_table)?;
                                 }
                             }
                         }
                         if output_tables.raw_len() == 1 {
-                            Ok(mlua::Value::Table(output_tables.get(1)?))
+                            Ok((mlua::Value::Table(output_tables.get(1)?), None))
                         } else {
-                            Ok(mlua::Value::Table(output_tables))
+                            Ok((mlua::Value::Table(output_tables), None))
                         }
                     }
-                    Err(e) => {
-                        eprintln!("{e}");
-                        Ok(mlua::Nil)
-                    }
+                    Err(e) => Ok((mlua::Nil, Some(e))),
                 },
-                Err(e) => {
-                    eprintln!("{e}");
-                    Ok(mlua::Nil)
-                }
+                Err(e) => Ok((
+                    mlua::Nil,
+                    Some(Error::new(ErrorKind::ConnectionFailed, &host).with_source(e)),
+                )),
             }
         },
     )?,
@@ -2726,166 +2888,222 @@ Key features include configuring network settings, sending custom traffic, and d
                     Ok(mlua::Value::Boolean(reg.unwrap().is_match(&str)))
                 })?,
         )?;
+        main_table.set("dns", lua::core_lua::dns_table(&self.lua)?)?;
         main_table.set(
-    "serialize",
-    self.lua.create_function(
-        |lua, (value, fmt, options): (mlua::Value, String, Option<mlua::Table>)| {
-            let mut spaces = 4;
-            let mut pretty = true;
-            let mut depth = 100;
-            if let Some(options) = &options {
-                spaces = options.get::<u8>("spaces").unwrap_or(4);
-                pretty = options.get::<bool>("pretty").unwrap_or(true);
-                depth = options.get::<u8>("depth").unwrap_or(100);
-            }
-            match fmt.as_str() {
-                "json" => Ok(mlua::Value::String(lua.create_string(if pretty {
-                    to_json(value, depth).pretty(spaces as u16)
-                } else {
-                    to_json(value, depth).dump()
-                })?)),
-                "yaml" => {
-                    let mut yaml_str = String::new();
-                    let mut emitter = yaml_rust2::emitter::YamlEmitter::new(&mut yaml_str);
-                    emitter.dump(&to_yaml(value, depth)).unwrap();
-                    Ok(mlua::Value::String(lua.create_string(yaml_str)?))
-                }
-                "toml" => Ok(mlua::Value::String(lua.create_string(
-                    match if pretty {
-                        toml::to_string_pretty(&to_toml(value, depth))
-                    } else {
-                        toml::to_string(&to_toml(value, depth))
-                    } {
-                        Ok(toml_str) => toml_str,
-                        Err(e) => {
-                            eprintln!("Failed to serialize the giving table.\nError: {e}");
-                            return Ok(mlua::Value::Nil);
-                        }
-                    },
-                )?)),
-                "csv" => {
-                    if let mlua::Value::Table(table) = value {
-                        let csv_str = to_csv(
-                            table,
-                            if let Some(opts) = &options {
-                                opts.get::<Option<mlua::Table>>("headers").unwrap_or(None)
+            "serialize",
+            self.lua.create_function(
+                |lua, (value, fmt, options): (mlua::Value, String, Option<mlua::Table>)| {
+                    let mut spaces = 4;
+                    let mut pretty = true;
+                    let mut depth = 100;
+                    if let Some(options) = &options {
+                        spaces = options.get::<u8>("spaces").unwrap_or(4);
+                        pretty = options.get::<bool>("pretty").unwrap_or(true);
+                        depth = options.get::<u8>("depth").unwrap_or(100);
+                    }
+                    match fmt.as_str() {
+                        "json" => Ok((
+                            mlua::Value::String(lua.create_string(if pretty {
+                                to_json(value, depth).pretty(spaces as u16)
                             } else {
-                                None
-                            },
-                        );
-                        if csv_str == "" {
-                            Ok(mlua::Value::Nil)
-                        } else {
-                            Ok(mlua::Value::String(lua.create_string(csv_str.trim())?))
+                                to_json(value, depth).dump()
+                            })?),
+                            None,
+                        )),
+                        "yaml" => {
+                            let mut yaml_str = String::new();
+                            let mut emitter = yaml_rust2::emitter::YamlEmitter::new(&mut yaml_str);
+                            if let Err(e) = emitter.dump(&to_yaml(value, depth)) {
+                                return Ok((
+                                    mlua::Value::Nil,
+                                    Some(Error::new(ErrorKind::InvalidYaml, "").with_source(e)),
+                                ));
+                            }
+                            Ok((mlua::Value::String(lua.create_string(yaml_str)?), None))
                         }
-                    } else {
-                        Ok(mlua::Value::Nil)
-                    }
-                }
-                "xml" => {
-                    if let mlua::Value::Table(table) = value {
-                        let mut document = vec![];
-                        if table.sequence_values::<mlua::Value>().count()
-                            == table.pairs::<String, mlua::Value>().count()
-                        {
-                            for tag in table.sequence_values::<mlua::Value>() {
-                                if let Ok(mlua::Value::Table(table)) = tag {
-                                    if let Ok(num) = table.get::<f64>("version") {
-                                        let encoding = table.get::<String>("encoding").unwrap_or_default();
-                                        let standalone = table.get::<String>("standalone").unwrap_or_default();
-                                        document.push(Event::Decl(
-                                            BytesDecl::new(
-                                                &num.to_string(),
-                                                if encoding.is_empty() {
-                                                    None
-                                                } else {
-                                                    Some(&encoding)
-                                                },
-                                                if standalone.is_empty() {
-                                                    None
-                                                } else {
-                                                    Some(&standalone)
-                                                },
-                                            )
-                                            .into_owned(),
-                                        ));
-                                        if let Ok(doctype) = table.get::<String>("doctype") {
-                                            document.push(Event::DocType(BytesText::new(&doctype).into_owned()));
-                                        }
-                                    } else if let Ok(doctype) = table.get::<String>("doctype") {
-                                        document.push(Event::DocType(BytesText::new(&doctype).into_owned()));
-                                    } else {
-                                        to_xml(table, &mut document, 0, depth, pretty, spaces);
-                                    }
+                        "toml" => {
+                            let toml_str = to_toml(value, depth).and_then(|value| {
+                                if pretty {
+                                    toml::to_string_pretty(&value)
                                 } else {
-                                    continue;
+                                    toml::to_string(&value)
                                 }
+                                .map_err(|e| Error::new(ErrorKind::InvalidToml, "").with_source(e))
+                            });
+                            match toml_str {
+                                Ok(toml_str) => {
+                                    Ok((mlua::Value::String(lua.create_string(toml_str)?), None))
+                                }
+                                Err(e) => Ok((mlua::Value::Nil, Some(e))),
                             }
-                        } else {
-                            to_xml(table, &mut document, 0, depth, pretty, spaces);
                         }
-                        let mut writer = if pretty {
-                            Writer::new_with_indent(
-                                std::io::Cursor::new(vec![]),
-                                b' ',
-                                spaces as usize,
-                            )
-                        } else {
-                            Writer::new(std::io::Cursor::new(vec![]))
-                        };
-                        for event in document {
-                            if let Event::Text(_) = event {
-                                if let Err(e) = writer.write_indent() {
-                                    eprintln!("Failed to convert to XML.\nError: {e}");
-                                    return Ok(mlua::Value::Nil);
+                        "csv" => {
+                            if let mlua::Value::Table(table) = value {
+                                let csv_str = to_csv(
+                                    table,
+                                    if let Some(opts) = &options {
+                                        opts.get::<Option<mlua::Table>>("headers").unwrap_or(None)
+                                    } else {
+                                        None
+                                    },
+                                );
+                                match csv_str {
+                                    Ok(csv_str) => Ok((
+                                        mlua::Value::String(lua.create_string(csv_str.trim())?),
+                                        None,
+                                    )),
+                                    Err(e) => Ok((mlua::Value::Nil, Some(e))),
                                 }
-                            }
-                            if let Err(e) = writer.write_event(event) {
-                                eprintln!("Failed to convert to XML.\nError: {e}");
-                                return Ok(mlua::Value::Nil);
+                            } else {
+                                Ok((
+                                    mlua::Value::Nil,
+                                    Some(Error::new(ErrorKind::InvalidCsv, value.type_name())),
+                                ))
                             }
                         }
-                        if let Ok(str) = std::str::from_utf8(&writer.into_inner().into_inner())
-                        {
-                            return Ok(mlua::Value::String(lua.create_string(str)?));
+                        "xml" => {
+                            if let mlua::Value::Table(table) = value {
+                                let mut document = vec![];
+                                if table.sequence_values::<mlua::Value>().count()
+                                    == table.pairs::<String, mlua::Value>().count()
+                                {
+                                    for tag in table.sequence_values::<mlua::Value>() {
+                                        if let Ok(mlua::Value::Table(table)) = tag {
+                                            if let Ok(num) = table.get::<f64>("version") {
+                                                let encoding = table
+                                                    .get::<String>("encoding")
+                                                    .unwrap_or_default();
+                                                let standalone = table
+                                                    .get::<String>("standalone")
+                                                    .unwrap_or_default();
+                                                document.push(Event::Decl(
+                                                    BytesDecl::new(
+                                                        &num.to_string(),
+                                                        if encoding.is_empty() {
+                                                            None
+                                                        } else {
+                                                            Some(&encoding)
+                                                        },
+                                                        if standalone.is_empty() {
+                                                            None
+                                                        } else {
+                                                            Some(&standalone)
+                                                        },
+                                                    )
+                                                    .into_owned(),
+                                                ));
+                                                if let Ok(doctype) = table.get::<String>("doctype")
+                                                {
+                                                    document.push(Event::DocType(
+                                                        BytesText::new(&doctype).into_owned(),
+                                                    ));
+                                                }
+                                            } else if let Ok(doctype) =
+                                                table.get::<String>("doctype")
+                                            {
+                                                document.push(Event::DocType(
+                                                    BytesText::new(&doctype).into_owned(),
+                                                ));
+                                            } else if let Err(e) = to_xml(
+                                                table,
+                                                &mut document,
+                                                0,
+                                                depth,
+                                                pretty,
+                                                spaces,
+                                            ) {
+                                                return Ok((mlua::Value::Nil, Some(e)));
+                                            }
+                                        } else {
+                                            continue;
+                                        }
+                                    }
+                                } else if let Err(e) =
+                                    to_xml(table, &mut document, 0, depth, pretty, spaces)
+                                {
+                                    return Ok((mlua::Value::Nil, Some(e)));
+                                }
+                                let mut writer = if pretty {
+                                    Writer::new_with_indent(
+                                        std::io::Cursor::new(vec![]),
+                                        b' ',
+                                        spaces as usize,
+                                    )
+                                } else {
+                                    Writer::new(std::io::Cursor::new(vec![]))
+                                };
+                                for event in document {
+                                    if let Event::Text(_) = event {
+                                        if let Err(e) = writer.write_indent() {
+                                            return Ok((
+                                                mlua::Value::Nil,
+                                                Some(
+                                                    Error::new(ErrorKind::InvalidXml, "")
+                                                        .with_source(e),
+                                                ),
+                                            ));
+                                        }
+                                    }
+                                    if let Err(e) = writer.write_event(event) {
+                                        return Ok((
+                                            mlua::Value::Nil,
+                                            Some(
+                                                Error::new(ErrorKind::InvalidXml, "")
+                                                    .with_source(e),
+                                            ),
+                                        ));
+                                    }
+                                }
+                                return match String::from_utf8(writer.into_inner().into_inner()) {
+                                    Ok(str) => {
+                                        Ok((mlua::Value::String(lua.create_string(str)?), None))
+                                    }
+                                    Err(e) => Ok((
+                                        mlua::Value::Nil,
+                                        Some(Error::new(ErrorKind::InvalidXml, "").with_source(e)),
+                                    )),
+                                };
+                            }
+                            Ok((
+                                mlua::Value::Nil,
+                                Some(Error::new(ErrorKind::InvalidXml, value.type_name())),
+                            ))
                         }
+                        _ => Ok((
+                            mlua::Value::Nil,
+                            Some(Error::new(ErrorKind::UnsupportedFormat, fmt)),
+                        )),
                     }
-                    Ok(mlua::Value::Nil)
-                }
-                _ => {
-                    eprintln!("Invalid format type.\nCurrently supported formats are json/toml/yaml/xml/csv");
-                    Ok(mlua::Value::Nil)
                 },
-            }
-        },
-    )?,
-)?;
+            )?,
+        )?;
         main_table.set(
             "deserialize",
             self.lua
                 .create_function(|lua, (value, fmt): (String, String)| match fmt.as_str() {
                     "json" => match json::parse(&value) {
-                        Ok(json_val) => Ok(from_json(lua, &json_val)),
-                        Err(e) => {
-                            eprintln!("Failed to parse JSON string!: {e}");
-                            Ok(mlua::Value::Nil)
-                        }
+                        Ok(json_val) => Ok((from_json(lua, &json_val), None)),
+                        Err(e) => Ok((
+                            mlua::Value::Nil,
+                            Some(Error::new(ErrorKind::InvalidJson, "").with_source(e)),
+                        )),
                     },
                     "yaml" => match yaml_rust2::YamlLoader::load_from_str(&value) {
-                        Ok(yaml_val) => {
-                            Ok(from_yaml(lua, &yaml_rust2::yaml::Yaml::Array(yaml_val)))
-                        }
-                        Err(e) => {
-                            eprintln!("Failed to parse YAML string!: {e}");
-                            Ok(mlua::Value::Nil)
-                        }
+                        Ok(yaml_val) => Ok((
+                            from_yaml(lua, &yaml_rust2::yaml::Yaml::Array(yaml_val)),
+                            None,
+                        )),
+                        Err(e) => Ok((
+                            mlua::Value::Nil,
+                            Some(Error::new(ErrorKind::InvalidYaml, "").with_source(e)),
+                        )),
                     },
                     "toml" => match value.trim().parse::<toml::Table>() {
-                        Ok(toml_val) => Ok(from_toml(lua, &toml::Value::Table(toml_val))),
-                        Err(e) => {
-                            eprintln!("Failed to parse TOML string!: {e}");
-                            Ok(mlua::Value::Nil)
-                        }
+                        Ok(toml_val) => Ok((from_toml(lua, &toml::Value::Table(toml_val)), None)),
+                        Err(e) => Ok((
+                            mlua::Value::Nil,
+                            Some(Error::new(ErrorKind::InvalidToml, "").with_source(e)),
+                        )),
                     },
                     "xml" => {
                         let mut reader = Reader::from_str(value.trim());
@@ -2897,8 +3115,10 @@ Key features include configuring network settings, sending custom traffic, and d
                                 Ok(Event::Eof) => break,
                                 Ok(e) => events.push(e),
                                 Err(e) => {
-                                    eprintln!("Failed to parse XML.\nError: {e}");
-                                    return Ok(mlua::Value::Nil);
+                                    return Ok((
+                                        mlua::Value::Nil,
+                                        Some(Error::new(ErrorKind::InvalidXml, "").with_source(e)),
+                                    ));
                                 }
                             }
                         }
@@ -2933,36 +3153,43 @@ Key features include configuring network settings, sending custom traffic, and d
                                 let doctype_table = lua.create_table()?;
                                 let doctype = String::from_utf8(doctype.to_vec());
                                 if let Err(e) = doctype {
-                                    eprintln!("Faild to parse XML.\nError: {e}");
-                                    return Ok(mlua::Value::Nil);
+                                    return Ok((
+                                        mlua::Value::Nil,
+                                        Some(Error::new(ErrorKind::InvalidXml, "").with_source(e)),
+                                    ));
                                 }
                                 doctype_table.set("doctype", doctype.unwrap())?;
                                 tags.push(doctype_table)?;
                                 index += 1;
-                            } else if let Some((table, ind)) = from_xml(
-                                lua,
-                                &events,
-                                index,
-                                if let Event::Empty(_) = &events[index] {
-                                    true
-                                } else {
-                                    false
-                                },
-                            ) {
-                                index = ind + 1;
-                                tags.push(mlua::Value::Table(table))?;
                             } else {
-                                return Ok(mlua::Value::Nil);
+                                match from_xml(
+                                    lua,
+                                    &events,
+                                    index,
+                                    if let Event::Empty(_) = &events[index] {
+                                        true
+                                    } else {
+                                        false
+                                    },
+                                ) {
+                                    Ok((table, ind)) => {
+                                        index = ind + 1;
+                                        tags.push(mlua::Value::Table(table))?;
+                                    }
+                                    Err(e) => return Ok((mlua::Value::Nil, Some(e))),
+                                }
                             }
                         }
-                        Ok(mlua::Value::Table(tags))
+                        Ok((mlua::Value::Table(tags), None))
                     }
-                    "csv" => Ok(if let Some(table) = from_csv(lua, value) {
-                        mlua::Value::Table(table)
-                    } else {
-                        mlua::Value::Nil
-                    }),
-                    _ => Ok(mlua::Value::Nil),
+                    "csv" => match from_csv(lua, value) {
+                        Ok(table) => Ok((mlua::Value::Table(table), None)),
+                        Err(e) => Ok((mlua::Value::Nil, Some(e))),
+                    },
+                    _ => Ok((
+                        mlua::Value::Nil,
+                        Some(Error::new(ErrorKind::UnsupportedFormat, fmt)),
+                    )),
                 })?,
         )?;
         self.lua.globals().set("yrnu", main_table)?;
@@ -2973,14 +3200,14 @@ Key features include configuring network settings, sending custom traffic, and d
 fn main() {
     // Creating the main yrnu instance
     let yrnu = Yrnu::new(None).unwrap_or_else(|e| {
-        eprintln!("{e}");
+        eprintln!("{e:#}");
         std::process::exit(1)
     });
     let arg_matches = yrnu.get_matches();
     if let Some(script) = arg_matches.get_one::<String>("script") {
         // Run a Lua file as a Yrnu script
         if let Err(e) = yrnu.run_script(script) {
-            println!("{e}");
+            println!("{e:#}");
             std::process::exit(-1);
         };
     } else {
@@ -1,8 +1,53 @@
+use crate::core::{
+    Interface, InterfaceAddress, IpAddress, IpRange, IpSet, MacAddress, Mask, Network, Path, Url,
+    WildcardMask,
+};
+use crate::error::{Error, ErrorKind::*};
+use crate::port::PortRange;
 use hashlink::LinkedHashMap;
 use json::JsonValue;
 use quick_xml::events::{BytesEnd, BytesStart, BytesText, Event};
 use std::str::FromStr;
 use yaml_rust2::yaml::Yaml;
+/// The canonical string representation of a yrnu userdata value, the same one as of the `serde`
+/// feature
+enum Representation {
+    String(String),
+    List(Vec<String>),
+}
+/// Gets the representation of the yrnu userdata values, the name of an `Interface` and the list
+/// of networks of an `IpSet`, `None` for any other userdata
+fn from_userdata(userdata: &mlua::AnyUserData) -> Option<Representation> {
+    macro_rules! represent {
+        ($($ty:ty),+) => {
+            $(
+                if let Ok(value) = userdata.borrow::<$ty>() {
+                    return Some(Representation::String(value.to_string()));
+                }
+            )+
+        };
+    }
+    represent!(
+        MacAddress,
+        IpAddress,
+        Mask,
+        WildcardMask,
+        Network,
+        IpRange,
+        InterfaceAddress,
+        Path,
+        Url,
+        PortRange
+    );
+    if let Ok(interface) = userdata.borrow::<Interface>() {
+        return Some(Representation::String(interface.name().to_owned()));
+    }
+    if let Ok(set) = userdata.borrow::<IpSet<mlua::Value>>() {
+        let networks = set.networks().iter().map(Network::to_string).collect();
+        return Some(Representation::List(networks));
+    }
+    None
+}
 pub fn to_json(val: mlua::Value, depth: u8) -> JsonValue {
     match val {
         mlua::Value::String(string) => JsonValue::String(string.to_string_lossy()),
@@ -44,6 +89,13 @@ pub fn to_json(val: mlua::Value, depth: u8) -> JsonValue {
                 JsonValue::Object(obj)
             }
         }
+        mlua::Value::UserData(userdata) => match from_userdata(&userdata) {
+            Some(Representation::String(value)) => JsonValue::String(value),
+            Some(Representation::List(values)) => {
+                JsonValue::Array(values.into_iter().map(JsonValue::String).collect())
+            }
+            None => JsonValue::Null,
+        },
         _ => JsonValue::Null,
     }
 }
@@ -115,6 +167,13 @@ pub fn to_yaml(val: mlua::Value, depth: u8) -> Yaml {
                 Yaml::Hash(obj)
             }
         }
+        mlua::Value::UserData(userdata) => match from_userdata(&userdata) {
+            Some(Representation::String(value)) => Yaml::String(value),
+            Some(Representation::List(values)) => {
+                Yaml::Array(values.into_iter().map(Yaml::String).collect())
+            }
+            None => Yaml::Null,
+        },
         _ => Yaml::Null,
     }
 }
@@ -145,8 +204,9 @@ pub fn from_yaml(lua: &mlua::Lua, yaml_val: &Yaml) -> mlua::Value {
         _ => mlua::Value::Nil,
     }
 }
-pub fn to_toml(val: mlua::Value, depth: u8) -> toml::Value {
-    match val {
+/// Converts a Lua value into a toml value, fails on a userdata value that is not one of yrnu
+pub fn to_toml(val: mlua::Value, depth: u8) -> Result<toml::Value, Error> {
+    Ok(match val {
         mlua::Value::String(string) => {
             if let Ok(datatime) = toml::value::Datetime::from_str(&string.to_string_lossy()) {
                 toml::Value::Datetime(datatime)
@@ -159,7 +219,7 @@ pub fn to_toml(val: mlua::Value, depth: u8) -> toml::Value {
         mlua::Value::Integer(num) => toml::Value::Integer(num.into()),
         mlua::Value::Table(table) => {
             if depth == 0 {
-                return toml::Value::Array(vec![]);
+                return Ok(toml::Value::Array(vec![]));
             }
             let mut is_arr = true;
             let mut key;
@@ -178,9 +238,9 @@ pub fn to_toml(val: mlua::Value, depth: u8) -> toml::Value {
             for pair in table.pairs::<String, mlua::Value>() {
                 if let Ok((key, val)) = pair {
                     if is_arr {
-                        values.push(to_toml(val, depth - 1))
+                        values.push(to_toml(val, depth - 1)?)
                     } else {
-                        obj.insert(key, to_toml(val, depth - 1));
+                        obj.insert(key, to_toml(val, depth - 1)?);
                     }
                 } else {
                     values.push(toml::Value::String(String::new()))
@@ -192,8 +252,15 @@ pub fn to_toml(val: mlua::Value, depth: u8) -> toml::Value {
                 toml::Value::Table(obj)
             }
         }
+        mlua::Value::UserData(userdata) => match from_userdata(&userdata) {
+            Some(Representation::String(value)) => toml::Value::String(value),
+            Some(Representation::List(values)) => {
+                toml::Value::Array(values.into_iter().map(toml::Value::String).collect())
+            }
+            None => return Err(Error::new(InvalidToml, "userdata")),
+        },
         _ => toml::Value::String(String::new()),
-    }
+    })
 }
 pub fn from_toml(lua: &mlua::Lua, yaml_val: &toml::Value) -> mlua::Value {
     match yaml_val {
@@ -248,9 +315,9 @@ pub fn to_xml(
     max_depth: u8,
     pretty: bool,
     spaces: u8,
-) {
+) -> Result<(), Error> {
     if depth == max_depth {
-        return;
+        return Ok(());
     }
     if let Ok(comment) = table.get::<String>("comment") {
         document.push(Event::Comment(BytesText::new(&comment).into_owned()));
@@ -270,8 +337,9 @@ pub fn to_xml(
             let (mut key, mut value);
             for pair in attributes.pairs::<String, String>() {
                 if pair.is_err() {
-                    eprintln!("Faild to convert to XML.\nError: {}", pair.err().unwrap());
-                    return;
+                    return Err(
+                        Error::new(InvalidXml, &name).with_source(pair.err().unwrap().to_string())
+                    );
                 } else {
                     (key, value) = pair.unwrap();
                     start.push_attribute((key.as_str(), value.as_str()));
@@ -299,7 +367,7 @@ pub fn to_xml(
                 }
                 if child.is_table() {
                     child_table = child.as_table().unwrap().to_owned();
-                    to_xml(child_table, &mut doc, depth + 1, max_depth, pretty, spaces);
+                    to_xml(child_table, doc, depth + 1, max_depth, pretty, spaces)?;
                 } else if child.to_string().is_ok() {
                     if pretty {
                         doc.push(Event::Text(
@@ -323,103 +391,90 @@ pub fn to_xml(
             doc.push(Event::End(BytesEnd::new(name)));
         }
     }
+    Ok(())
 }
 pub fn from_xml(
     lua: &mlua::Lua,
     xml_vec: &Vec<Event>,
     index: usize,
     is_empty: bool,
-) -> Option<(mlua::Table, usize)> {
+) -> Result<(mlua::Table, usize), Error> {
     if let Event::Comment(text) = &xml_vec[index] {
         let text = String::from_utf8(text.to_vec());
         if let Err(e) = text {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
         let comment_table = lua.create_table();
 
         if let Err(e) = comment_table {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
         let comment_table = comment_table.unwrap();
         if let Err(e) = comment_table.set("comment", text.unwrap()) {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
-        return Some((comment_table, index));
+        return Ok((comment_table, index));
     }
     let tag_table = lua.create_table();
     if let Err(e) = tag_table {
-        eprintln!("Faild to parse XML.\nError: {e}");
-        return None;
+        return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
     }
     let tag_table = tag_table.unwrap();
     if is_empty {
         if let Err(e) = tag_table.set("self_closed", true) {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
     }
     let attributes = lua.create_table();
     if let Err(e) = attributes {
-        eprintln!("Faild to parse XML.\nError: {e}");
-        return None;
+        return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
     }
     let attributes = attributes.unwrap();
     let children = lua.create_table();
     if let Err(e) = children {
-        eprintln!("Faild to parse XML.\nError: {e}");
-        return None;
+        return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
     }
     let children = children.unwrap();
     let mut ind = index + 1;
     if let Event::Start(event) | Event::Empty(event) = &xml_vec[index] {
         let name = String::from_utf8(event.name().into_inner().to_vec());
         if let Err(e) = name {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
         if let Err(e) = tag_table.set("name", name.unwrap()) {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
         let mut attribute;
         let (mut key, mut val);
         for attr in event.attributes() {
             if attr.is_err() {
-                eprintln!("Faild to parse XML.\nError: {}", attr.err().unwrap());
-                return None;
+                return Err(Error::new(InvalidXml, "").with_source(attr.err().unwrap().to_string()));
             }
             attribute = attr.unwrap();
             key = String::from_utf8(attribute.key.into_inner().to_vec());
             if key.is_err() {
-                eprintln!("Faild to parse XML.\nError: {}", key.err().unwrap());
-                return None;
+                return Err(Error::new(InvalidXml, "").with_source(key.err().unwrap().to_string()));
             }
             val = attribute.unescape_value();
             if val.is_err() {
-                eprintln!("Faild to parse XML.\nError: {}", val.err().unwrap());
-                return None;
+                return Err(Error::new(InvalidXml, "").with_source(val.err().unwrap().to_string()));
             }
             if let Err(e) = attributes.set(key.unwrap(), val.unwrap()) {
-                eprintln!("Faild to parse XML.\nError: {e}");
-                return None;
+                return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
             }
         }
         if let Err(e) = tag_table.set("attributes", attributes) {
-            eprintln!("Faild to parse XML.\nError: {e}");
-            return None;
+            return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
         }
         if is_empty {
-            return Some((tag_table, index));
+            return Ok((tag_table, index));
         }
     }
     let mut tag;
     let mut depth = 0;
     loop {
         if ind >= xml_vec.len() {
-            return Some((tag_table, ind));
+            return Ok((tag_table, ind));
         }
         match &xml_vec[ind] {
             Event::Start(_) | Event::Empty(_) | Event::Comment(_) => {
@@ -438,24 +493,19 @@ pub fn from_xml(
                         false
                     },
                 );
-                if tag.is_none() {
-                    return None;
-                }
-                ind = tag.as_ref().unwrap().1;
-                if let Err(e) = children.push(tag.unwrap().0) {
-                    eprintln!("Faild to parse XML.\nError: {e}");
-                    return None;
+                let (tag, tag_index) = tag?;
+                ind = tag_index;
+                if let Err(e) = children.push(tag) {
+                    return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
                 }
             }
             Event::Text(text) => {
                 let text = String::from_utf8(text.to_vec());
                 if let Err(e) = text {
-                    eprintln!("Faild to parse XML.\nError: {e}");
-                    return None;
+                    return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
                 }
                 if let Err(e) = children.push(text.unwrap()) {
-                    eprintln!("Faild to parse XML.\nError: {e}");
-                    return None;
+                    return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
                 }
             }
             Event::End(_) => {
@@ -464,17 +514,16 @@ pub fn from_xml(
                     continue;
                 }
                 if let Err(e) = tag_table.set("children", children) {
-                    eprintln!("Faild to parse XML.\nError: {e}");
-                    return None;
+                    return Err(Error::new(InvalidXml, "").with_source(e.to_string()));
                 }
-                return Some((tag_table, ind));
+                return Ok((tag_table, ind));
             }
-            _ => return None,
+            _ => return Err(Error::new(InvalidXml, "")),
         }
         ind += 1;
     }
 }
-pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
+pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> Result<String, Error> {
     let mut writer = csv::Writer::from_writer(vec![]);
     let mut field_values = vec![];
     if headers.is_some()
@@ -488,14 +537,12 @@ pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
         for header in headers.as_ref().unwrap().sequence_values::<String>() {
             if let Ok(header) = header {
                 if let Err(e) = writer.write_field(&header) {
-                    eprintln!("Faild to convert header {header} to CSV.\nError: {e}");
-                    return String::new();
+                    return Err(Error::new(InvalidCsv, format!("header {header}")).with_source(e));
                 }
             }
         }
         if let Err(e) = writer.write_record(None::<&[u8]>) {
-            eprintln!("Faild to convert to CSV.\nError: {e}");
-            return String::new();
+            return Err(Error::new(InvalidCsv, "").with_source(e));
         }
         for (i, record) in table.sequence_values::<mlua::Value>().enumerate() {
             if let Ok(mlua::Value::Table(record)) = record {
@@ -512,28 +559,28 @@ pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
                                     .unwrap(),
                             )
                         } else {
-                            eprintln!("Faild to convert to CSV.\nError: {}", header.err().unwrap());
-                            return String::new();
+                            return Err(Error::new(InvalidCsv, "")
+                                .with_source(header.err().unwrap().to_string()));
                         }
                     }
                 } else {
                     for (j, field) in record.sequence_values::<String>().enumerate() {
                         if let Ok(field) = field {
                             if let Err(e) = writer.write_field(field) {
-                                eprintln!(
-                                    "Faild to convert field {j} in record {i} to CSV.\nError: {e}"
-                                );
-                                return String::new();
+                                return Err(Error::new(
+                                    InvalidCsv,
+                                    format!("field {j} in record {i}"),
+                                )
+                                .with_source(e));
                             }
                         } else {
-                            eprintln!("Faild to convert to CSV.\nError: {}", field.err().unwrap());
-                            return String::new();
+                            return Err(Error::new(InvalidCsv, "")
+                                .with_source(field.err().unwrap().to_string()));
                         }
                     }
                 }
                 if let Err(e) = writer.write_record(&field_values) {
-                    eprintln!("Faild to convert record number {i} to CSV.\nError: {e}");
-                    return String::new();
+                    return Err(Error::new(InvalidCsv, format!("record number {i}")).with_source(e));
                 }
                 field_values.clear();
             } else {
@@ -552,37 +599,30 @@ pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
                         if record.sequence_values::<String>().count()
                             != record.pairs::<String, String>().count()
                         {
-                            eprintln!(
-                                "Faild to convert to CSV.\nError: Record number {i} is invalid"
-                            );
-                            return String::new();
+                            return Err(Error::new(InvalidCsv, format!("record number {i}")));
                         }
                         if record.sequence_values::<String>().count() != 0 {
                             for (j, field) in record.sequence_values::<String>().enumerate() {
                                 match field {
                                     Ok(field) => fields_values.push(field),
                                     Err(e) => {
-                                        eprintln!("Faild to convert field {j} in record {i} to CSV.\nError: {e}");
-                                        return String::new();
+                                        return Err(Error::new(
+                                            InvalidCsv,
+                                            format!("field {j} in record {i}"),
+                                        )
+                                        .with_source(e.to_string()));
                                     }
                                 }
                             }
                             if let Err(e) = writer.write_record(&fields_values) {
-                                eprintln!("Faild to convert to CSV.\nError: {e}");
-                                return String::new();
+                                return Err(Error::new(InvalidCsv, "").with_source(e));
                             }
                             fields_values.clear();
                         } else {
-                            eprintln!(
-                                "Faild to convert to CSV.\nError: Record number {i} is invalid"
-                            );
-                            return String::new();
+                            return Err(Error::new(InvalidCsv, format!("record number {i}")));
                         }
                     } else {
-                        eprintln!(
-                            "Faild to convert record number {i} to CSV.\nError: Invalid table"
-                        );
-                        return String::new();
+                        return Err(Error::new(InvalidCsv, format!("record number {i}")));
                     }
                 }
             } else if first_table.pairs::<String, String>().count() != 0 {
@@ -595,8 +635,7 @@ pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
                     field_values.push(header);
                 }
                 if let Err(e) = writer.write_record(&field_values) {
-                    eprintln!("Faild to convert to CSV.\nError: {e}");
-                    return String::new();
+                    return Err(Error::new(InvalidCsv, "").with_source(e));
                 }
                 field_values.clear();
                 for (i, record) in table.sequence_values::<mlua::Value>().enumerate() {
@@ -610,46 +649,40 @@ pub fn to_csv(table: mlua::Table, headers: Option<mlua::Table>) -> String {
                                 match record.get::<String>(header.as_str()) {
                                     Ok(field) => field_values.push(field),
                                     Err(e) => {
-                                        eprintln!("Faild to convert to CSV.\nError: {e}");
-                                        return String::new();
+                                        return Err(
+                                            Error::new(InvalidCsv, "").with_source(e.to_string())
+                                        );
                                     }
                                 }
                             }
                             if let Err(e) = writer.write_record(&field_values) {
-                                eprintln!("Faild to convert to CSV.\nError: {e}");
-                                return String::new();
+                                return Err(Error::new(InvalidCsv, "").with_source(e));
                             }
                             field_values.clear();
                         }
                     } else {
-                        eprintln!("Faild to convert to CSV.\nError: Record number {i} is invalid");
-                        return String::new();
+                        return Err(Error::new(InvalidCsv, format!("record number {i}")));
                     }
                 }
             } else {
-                eprintln!("Faild to convert table to CSV.\nError: Invalid values for csv table");
-                return String::new();
+                return Err(Error::new(InvalidCsv, ""));
             }
         } else {
-            eprintln!("Faild to convert table to CSV.\nError: Invalid values for csv table");
-            return String::new();
-        }
-    }
-    if let Ok(writer) = writer.into_inner() {
-        if let Ok(csv_str) = String::from_utf8(writer) {
-            return csv_str;
+            return Err(Error::new(InvalidCsv, ""));
         }
     }
-    String::new()
+    let writer = writer
+        .into_inner()
+        .map_err(|e| Error::new(InvalidCsv, "").with_source(e.to_string()))?;
+    String::from_utf8(writer).map_err(|e| Error::new(InvalidCsv, "").with_source(e))
 }
-pub fn from_csv(lua: &mlua::Lua, csv_str: String) -> Option<mlua::Table> {
+pub fn from_csv(lua: &mlua::Lua, csv_str: String) -> Result<mlua::Table, Error> {
     let mut reader = csv::ReaderBuilder::new().from_reader(csv_str.as_bytes());
     let headers = reader.headers();
     let csv_table = lua.create_table();
     let (mut record_table_res, mut record_table);
     if let Err(e) = csv_table {
-        eprintln!("Faild to parse CSV\nError:{e}");
-        return None;
+        return Err(Error::new(InvalidCsv, "").with_source(e.to_string()));
     }
     let csv_table = csv_table.unwrap();
     let headers = if let Ok(headers) = headers {
@@ -680,8 +713,7 @@ pub fn from_csv(lua: &mlua::Lua, csv_str: String) -> Option<mlua::Table> {
                 break;
             }
             if let Err(e) = record_table.set(headers[i].as_str(), field) {
-                eprintln!("Faild to parse CSV\nError:{e}");
-                return None;
+                return Err(Error::new(InvalidCsv, "").with_source(e.to_string()));
             }
         }
         if let Err(e) = csv_table.push(record_table) {
@@ -693,5 +725,5 @@ pub fn from_csv(lua: &mlua::Lua, csv_str: String) -> Option<mlua::Table> {
     if is_err {
         eprintln!("Some records has been skiped as a result of parsing error with those records");
     }
-    Some(csv_table)
+    Ok(csv_table)
 }
@@ -1,3 +1,10 @@
+use crate::error::{Error, ErrorKind::*};
+use core::fmt;
+use mlua::FromLua;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::sync::{OnceLock, RwLock};
+
 pub const FTP_DATA: u16 = 20;
 pub const FTP: u16 = 21;
 pub const SSH: u16 = 22;
@@ -18,3 +25,433 @@ pub const SYSLOG: u16 = 514;
 pub const RIP: u16 = 520;
 pub const FTPS_DATA: u16 = 989;
 pub const FTPS: u16 = 990;
+
+/// # Protocol
+/// `Protocol` - transport protocols a service is registered for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Protocol {
+    Tcp,
+    Udp,
+    Sctp,
+}
+
+/// # Service
+/// `Service` - entry of the service name and transport protocol port number registry
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct Service {
+    name: String,
+    port: u16,
+    protocol: Protocol,
+    aliases: Vec<String>,
+    description: String,
+}
+
+/// # Services
+/// `Services` - registry of services, looked up by name or by port number
+#[derive(Debug, Clone, Default)]
+pub struct Services {
+    services: Vec<Service>,
+}
+
+/// # PortRange
+/// `PortRange` - list of ports and port ranges, like `22,80,8000-8100`
+#[derive(Debug, Clone, PartialEq, FromLua)]
+pub struct PortRange {
+    ranges: Vec<(u16, u16)>,
+}
+
+/// A curated list of common services shipped with yrnu, as (name, port, protocols, aliases,
+/// description) sorted by port and name. The names and ports follow the IANA service registry,
+/// but the list is not complete and some aliases (like `dns`, `smb` or `rdp`) are common names
+/// and not IANA ones
+#[rustfmt::skip]
+const CURATED_SERVICES: &[(&str, u16, &str, &str, &str)] = &[
+    ("tcpmux", 1, "tcp udp", "", "TCP Port Service Multiplexer"),
+    ("echo", 7, "tcp udp sctp", "", "Echo"),
+    ("discard", 9, "tcp udp sctp", "sink null", "Discard"),
+    ("daytime", 13, "tcp udp", "", "Daytime"),
+    ("qotd", 17, "tcp udp", "quote", "Quote of the Day"),
+    ("chargen", 19, "tcp udp", "ttytst source", "Character Generator"),
+    ("ftp-data", 20, "tcp udp sctp", "", "File Transfer [Default Data]"),
+    ("ftp", 21, "tcp udp sctp", "", "File Transfer Protocol [Control]"),
+    ("ssh", 22, "tcp udp sctp", "", "The Secure Shell (SSH) Protocol"),
+    ("telnet", 23, "tcp udp", "", "Telnet"),
+    ("smtp", 25, "tcp udp", "mail", "Simple Mail Transfer"),
+    ("time", 37, "tcp udp", "timserver", "Time"),
+    ("whois", 43, "tcp udp", "nicname", "Who Is"),
+    ("tacacs", 49, "tcp udp", "", "Login Host Protocol (TACACS)"),
+    ("domain", 53, "tcp udp", "dns", "Domain Name Server"),
+    ("bootps", 67, "tcp udp", "dhcps", "Bootstrap Protocol Server"),
+    ("bootpc", 68, "tcp udp", "dhcpc", "Bootstrap Protocol Client"),
+    ("tftp", 69, "tcp udp", "", "Trivial File Transfer"),
+    ("gopher", 70, "tcp udp", "", "Gopher"),
+    ("finger", 79, "tcp udp", "", "Finger"),
+    ("http", 80, "tcp udp sctp", "www www-http", "World Wide Web HTTP"),
+    ("kerberos", 88, "tcp udp", "kerberos5 krb5 kerberos-sec", "Kerberos"),
+    ("pop3", 110, "tcp udp", "pop-3", "Post Office Protocol - Version 3"),
+    ("sunrpc", 111, "tcp udp", "portmapper rpcbind", "SUN Remote Procedure Call"),
+    ("auth", 113, "tcp udp", "ident authentication", "Authentication Service"),
+    ("nntp", 119, "tcp udp", "readnews untp", "Network News Transfer Protocol"),
+    ("ntp", 123, "tcp udp", "", "Network Time Protocol"),
+    ("epmap", 135, "tcp udp", "loc-srv", "DCE endpoint resolution"),
+    ("netbios-ns", 137, "tcp udp", "", "NETBIOS Name Service"),
+    ("netbios-dgm", 138, "tcp udp", "", "NETBIOS Datagram Service"),
+    ("netbios-ssn", 139, "tcp udp", "", "NETBIOS Session Service"),
+    ("imap", 143, "tcp udp", "imap2", "Internet Message Access Protocol"),
+    ("snmp", 161, "tcp udp", "", "SNMP"),
+    ("snmptrap", 162, "tcp udp", "snmp-trap", "SNMPTRAP"),
+    ("bgp", 179, "tcp udp sctp", "", "Border Gateway Protocol"),
+    ("irc", 194, "tcp udp", "", "Internet Relay Chat Protocol"),
+    ("ldap", 389, "tcp udp", "", "Lightweight Directory Access Protocol"),
+    ("https", 443, "tcp udp sctp", "", "http protocol over TLS/SSL"),
+    ("microsoft-ds", 445, "tcp udp", "smb", "Microsoft-DS"),
+    ("kpasswd", 464, "tcp udp", "", "kpasswd"),
+    ("isakmp", 500, "tcp udp", "ike", "isakmp"),
+    ("biff", 512, "udp", "comsat", "used by mail system to notify users"),
+    ("exec", 512, "tcp", "", "remote process execution"),
+    ("login", 513, "tcp", "", "remote login a la telnet"),
+    ("who", 513, "udp", "whod", "maintains data bases showing who's logged in"),
+    ("shell", 514, "tcp", "cmd", "cmd like exec, but automatic authentication is performed"),
+    ("syslog", 514, "udp", "", "syslog"),
+    ("printer", 515, "tcp udp", "spooler", "spooler"),
+    ("router", 520, "udp", "rip route routed", "local routing process (on site)"),
+    ("ripng", 521, "tcp udp", "", "ripng"),
+    ("dhcpv6-client", 546, "tcp udp", "", "DHCPv6 Client"),
+    ("dhcpv6-server", 547, "tcp udp", "", "DHCPv6 Server"),
+    ("submission", 587, "tcp udp", "", "Message Submission"),
+    ("ipp", 631, "tcp udp", "", "IPP (Internet Printing Protocol)"),
+    ("ldaps", 636, "tcp udp", "", "ldap protocol over TLS/SSL (was sldap)"),
+    ("ldp", 646, "tcp udp", "", "Label Distribution Protocol"),
+    ("netconf-ssh", 830, "tcp", "", "NETCONF over SSH"),
+    ("rsync", 873, "tcp udp", "", "rsync"),
+    ("ftps-data", 989, "tcp udp", "", "ftp protocol, data, over TLS/SSL"),
+    ("ftps", 990, "tcp udp", "", "ftp protocol, control, over TLS/SSL"),
+    ("telnets", 992, "tcp udp", "", "telnet protocol over TLS/SSL"),
+    ("imaps", 993, "tcp udp", "", "IMAP over TLS protocol"),
+    ("pop3s", 995, "tcp udp", "", "POP3 over TLS protocol"),
+    ("socks", 1080, "tcp udp", "", "Socks"),
+    ("openvpn", 1194, "tcp udp", "", "OpenVPN"),
+    ("ms-sql-s", 1433, "tcp udp", "", "Microsoft-SQL-Server"),
+    ("l2tp", 1701, "tcp udp", "", "l2tp"),
+    ("pptp", 1723, "tcp udp", "", "pptp"),
+    ("radius", 1812, "tcp udp", "", "RADIUS"),
+    ("radius-acct", 1813, "tcp udp", "radacct", "RADIUS Accounting"),
+    ("hsrp", 1985, "tcp udp", "", "Hot Standby Router Protocol"),
+    ("nfs", 2049, "tcp udp sctp", "", "Network File System - Sun Microsystems"),
+    ("mysql", 3306, "tcp udp", "", "MySQL"),
+    ("ms-wbt-server", 3389, "tcp udp", "rdp", "MS WBT Server"),
+    ("bfd-control", 3784, "udp", "", "BFD Control Protocol"),
+    ("bfd-echo", 3785, "udp", "", "BFD Echo Protocol"),
+    ("ipsec-nat-t", 4500, "tcp udp", "", "IPsec NAT-Traversal"),
+    ("vxlan", 4789, "udp", "", "Virtual eXtensible Local Area Network (VXLAN)"),
+    ("sip", 5060, "tcp udp sctp", "", "SIP"),
+    ("sips", 5061, "tcp udp sctp", "", "SIP-TLS"),
+    ("mdns", 5353, "udp", "", "Multicast DNS"),
+    ("llmnr", 5355, "tcp udp", "", "LLMNR"),
+    ("postgresql", 5432, "tcp udp", "postgres", "PostgreSQL Database"),
+    ("amqp", 5672, "tcp udp sctp", "", "AMQP"),
+    ("x11", 6000, "tcp udp", "", "X Window System"),
+    ("http-alt", 8080, "tcp udp", "webcache", "HTTP Alternate (see port 80)"),
+];
+
+impl Protocol {
+    /// Returns every protocol
+    fn all() -> [Protocol; 3] {
+        [Protocol::Tcp, Protocol::Udp, Protocol::Sctp]
+    }
+}
+
+impl Display for Protocol {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Protocol::Tcp => write!(f, "tcp"),
+            Protocol::Udp => write!(f, "udp"),
+            Protocol::Sctp => write!(f, "sctp"),
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "tcp" => Ok(Protocol::Tcp),
+            "udp" => Ok(Protocol::Udp),
+            "sctp" => Ok(Protocol::Sctp),
+            _ => Err(Error::new(InvalidProtocol, s)),
+        }
+    }
+}
+
+impl Service {
+    /// Creates a new Service instance
+    pub fn new(
+        name: &str,
+        port: u16,
+        protocol: Protocol,
+        aliases: &[&str],
+        description: &str,
+    ) -> Service {
+        Service {
+            name: name.to_string(),
+            port,
+            protocol,
+            aliases: aliases.iter().map(|alias| alias.to_string()).collect(),
+            description: description.to_string(),
+        }
+    }
+    /// Returns the service with the giving name or alias from the shared registry, any protocol
+    /// matches when none is giving
+    pub fn by_name(name: &str, protocol: Option<Protocol>) -> Option<Service> {
+        Services::shared()
+            .read()
+            .unwrap()
+            .by_name(name, protocol)
+            .cloned()
+    }
+    /// Returns the service registered for a port in the shared registry, any protocol matches
+    /// when none is giving
+    pub fn by_port(port: u16, protocol: Option<Protocol>) -> Option<Service> {
+        Services::shared()
+            .read()
+            .unwrap()
+            .by_port(port, protocol)
+            .cloned()
+    }
+    /// Returns every service of the shared registry
+    pub fn all() -> Vec<Service> {
+        Services::shared().read().unwrap().all().to_owned()
+    }
+    /// Loads a file in the `/etc/services` format, like `/etc/services` itself, into the shared
+    /// registry, only the services registered for a port and protocol the registry does not have
+    /// are added
+    pub fn load_services_file(path: impl AsRef<std::path::Path>) -> Result<(), Error> {
+        let services = Services::load(path)?;
+        Services::shared().write().unwrap().extend(services);
+        Ok(())
+    }
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+    pub fn protocol(&self) -> Protocol {
+        self.protocol
+    }
+    pub fn aliases(&self) -> &Vec<String> {
+        &self.aliases
+    }
+    pub fn description(&self) -> &String {
+        &self.description
+    }
+    /// Checks if the service is known by the giving name, the name or one of the aliases
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+            || self
+                .aliases
+                .iter()
+                .any(|alias| alias.eq_ignore_ascii_case(name))
+    }
+}
+
+impl Display for Service {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}/{}", self.name, self.port, self.protocol)
+    }
+}
+
+impl Services {
+    /// Creates a new empty Services instance
+    pub fn new() -> Services {
+        Services::default()
+    }
+    /// Returns the curated services list shipped with yrnu
+    pub fn embedded() -> Services {
+        let mut services = Services::new();
+        for (name, port, protocols, aliases, description) in CURATED_SERVICES {
+            let aliases = aliases.split_whitespace().collect::<Vec<&str>>();
+            for protocol in protocols.split_whitespace().flat_map(Protocol::from_str) {
+                services.insert(Service::new(name, *port, protocol, &aliases, description));
+            }
+        }
+        services
+    }
+    /// Returns the registry used by the `Service` lookups, it starts as the curated list and
+    /// more services are added with `Service::load_services_file`
+    fn shared() -> &'static RwLock<Services> {
+        static SERVICES: OnceLock<RwLock<Services>> = OnceLock::new();
+        SERVICES.get_or_init(|| RwLock::new(Services::embedded()))
+    }
+    /// Reads a file in the `/etc/services` format
+    pub fn load(path: impl AsRef<std::path::Path>) -> Result<Services, Error> {
+        let path = path.as_ref();
+        let data = std::fs::read_to_string(path)
+            .map_err(|e| Error::new(InvalidServicesFile, path.display()).with_source(e))?;
+        Ok(Self::parse(&data))
+    }
+    /// Parses services in the `/etc/services` format, `name port/protocol aliases # comment`,
+    /// the invalid lines are left out
+    pub fn parse(data: &str) -> Services {
+        let mut services = Services::new();
+        for line in data.lines() {
+            let (entry, comment) = line.split_once('#').unwrap_or((line, ""));
+            let fields = entry.split_whitespace().collect::<Vec<&str>>();
+            if fields.len() < 2 {
+                continue;
+            }
+            let Some((port, protocol)) = fields[1].split_once('/') else {
+                continue;
+            };
+            if let (Ok(port), Ok(protocol)) = (port.parse::<u16>(), protocol.parse::<Protocol>()) {
+                services.insert(Service::new(
+                    fields[0],
+                    port,
+                    protocol,
+                    &fields[2..],
+                    comment.trim(),
+                ));
+            }
+        }
+        services
+    }
+    /// Adds a service to the registry
+    pub fn insert(&mut self, service: Service) {
+        self.services.push(service);
+    }
+    /// Adds the services of another registry that are registered for a port and protocol this
+    /// one does not have
+    pub fn extend(&mut self, other: Services) {
+        for service in other.services {
+            if self.by_port(service.port, Some(service.protocol)).is_none() {
+                self.insert(service);
+            }
+        }
+    }
+    /// Returns the first service with the giving name or alias
+    pub fn by_name(&self, name: &str, protocol: Option<Protocol>) -> Option<&Service> {
+        self.services.iter().find(|service| {
+            service.is_named(name) && protocol.is_none_or(|protocol| service.protocol == protocol)
+        })
+    }
+    /// Returns the first service registered for the giving port
+    pub fn by_port(&self, port: u16, protocol: Option<Protocol>) -> Option<&Service> {
+        self.services.iter().find(|service| {
+            service.port == port && protocol.is_none_or(|protocol| service.protocol == protocol)
+        })
+    }
+    /// Returns every service of the registry
+    pub fn all(&self) -> &Vec<Service> {
+        &self.services
+    }
+    pub fn len(&self) -> usize {
+        self.services.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.services.is_empty()
+    }
+}
+
+/// Parses a port given as a number or as a service name, like `22` or `ssh`
+pub fn parse_port(port: &str) -> Result<u16, Error> {
+    let port = port.trim();
+    match port.parse::<u16>() {
+        Ok(0) => Err(Error::new(InvalidPort, port)),
+        Ok(port) => Ok(port),
+        Err(_) => Protocol::all()
+            .into_iter()
+            .find_map(|protocol| Service::by_name(port, Some(protocol)))
+            .map(|service| service.port)
+            .ok_or_else(|| Error::new(InvalidPort, port)),
+    }
+}
+
+impl PortRange {
+    /// Creates a new PortRange instance from a list of inclusive (first, last) ranges, the ranges
+    /// are sorted and the overlapping ones are merged
+    pub fn new(ranges: &[(u16, u16)]) -> Result<PortRange, Error> {
+        if let Some((first, last)) = ranges
+            .iter()
+            .find(|(first, last)| *first == 0 || first > last)
+        {
+            return Err(Error::new(InvalidPortRange, format!("{first}-{last}")));
+        }
+        let mut sorted = ranges.to_vec();
+        sorted.sort();
+        let mut merged: Vec<(u16, u16)> = vec![];
+        for (first, last) in sorted {
+            match merged.last_mut() {
+                Some(previous) if first as u32 <= previous.1 as u32 + 1 => {
+                    previous.1 = previous.1.max(last)
+                }
+                _ => merged.push((first, last)),
+            }
+        }
+        Ok(PortRange { ranges: merged })
+    }
+    /// Checks if a giving port is in the list
+    pub fn contains(&self, port: u16) -> bool {
+        self.ranges
+            .iter()
+            .any(|(first, last)| (*first..=*last).contains(&port))
+    }
+    /// Returns the ranges of the list, sorted and merged
+    pub fn ranges(&self) -> &Vec<(u16, u16)> {
+        &self.ranges
+    }
+    /// Returns every port of the list in order
+    pub fn ports(&self) -> impl Iterator<Item = u16> + '_ {
+        self.ranges.iter().flat_map(|(first, last)| *first..=*last)
+    }
+    /// Returns the number of ports in the list
+    pub fn len(&self) -> usize {
+        self.ranges
+            .iter()
+            .map(|(first, last)| (last - first) as usize + 1)
+            .sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+}
+
+impl Display for PortRange {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let ranges = self
+            .ranges
+            .iter()
+            .map(|(first, last)| match first == last {
+                true => first.to_string(),
+                false => format!("{}-{}", first, last),
+            })
+            .collect::<Vec<String>>();
+        write!(f, "{}", ranges.join(","))
+    }
+}
+
+impl FromStr for PortRange {
+    type Err = Error;
+    /// Parses a comma separated list of ports, port ranges and service names, like
+    /// `22,80,8000-8100` or `ssh,https`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || Error::new(InvalidPortRange, s);
+        let mut ranges = vec![];
+        for part in s.split(',') {
+            let range = match part.split_once('-') {
+                Some((first, last)) if first.trim().parse::<u16>().is_ok() => (
+                    first.trim().parse::<u16>().map_err(|_| invalid())?,
+                    last.trim().parse::<u16>().map_err(|_| invalid())?,
+                ),
+                _ => {
+                    let port = parse_port(part).map_err(|e| invalid().with_source(e))?;
+                    (port, port)
+                }
+            };
+            ranges.push(range);
+        }
+        PortRange::new(&ranges).map_err(|e| invalid().with_source(e))
+    }
+}
+
+#[cfg(feature = "serde")]
+serde_from_str!(Protocol, PortRange);
@@ -1,5 +1,13 @@
+use std::error::Error as _;
 use std::str::FromStr;
-use yrnu::core::{self, Interface, IpAddress, IpKind, IpVersion, MacAddress, Mask, Network};
+use yrnu::core::{
+    self, Interface, InterfaceAddress, InterfaceState, IpAddress, IpKind, IpRange, IpSet,
+    IpVersion, MacAddress, MacNotation, Mask, Neighbor, NeighborState, Network, Oui, OuiDatabase,
+    Route, WildcardMask,
+};
+use yrnu::dns;
+use yrnu::port::{self, PortRange, Protocol, Service, Services};
+use yrnu::{Error, ErrorKind};
 // IpVersion tests
 #[test]
 fn is_v4_test() {
@@ -28,7 +36,79 @@ fn is_v6_test() {
 
 // IpKind tests
 #[test]
-fn get_kind_test() {}
+fn get_kind_test() {
+    let kind = |address: &str| IpKind::get_kind(address).unwrap();
+    assert_eq!(kind("8.8.8.8"), IpKind::Public);
+    assert_eq!(kind("10.1.2.3"), IpKind::Private);
+    assert_eq!(kind("127.0.0.1"), IpKind::Loopback);
+    assert_eq!(kind("169.254.1.1"), IpKind::Apipa);
+    assert_eq!(kind("224.0.0.5"), IpKind::Multicast);
+    assert_eq!(kind("0.0.0.0"), IpKind::Unspecified);
+    assert_eq!(kind("100.64.1.1"), IpKind::Shared);
+    assert_eq!(kind("192.0.2.10"), IpKind::Documentation);
+    assert_eq!(kind("198.51.100.1"), IpKind::Documentation);
+    assert_eq!(kind("203.0.113.200"), IpKind::Documentation);
+    assert_eq!(kind("198.19.0.1"), IpKind::Benchmarking);
+    assert_eq!(kind("240.0.0.1"), IpKind::Reserved);
+    assert_eq!(kind("255.255.255.255"), IpKind::Broadcast);
+    assert_eq!(kind("2001:db8::1"), IpKind::Documentation);
+    assert_eq!(kind("2002:c000:204::1"), IpKind::SixToFour);
+    assert_eq!(kind("2001:0:4136:e378::1"), IpKind::Teredo);
+    assert_eq!(kind("::ffff:10.0.0.1"), IpKind::Ipv4Mapped);
+    assert_eq!(kind("2001:20::1"), IpKind::Orchid);
+    assert_eq!(kind("fd12:3456::1"), IpKind::Uniqelocal);
+    assert_eq!(kind("fe80::1"), IpKind::Linklocal);
+    assert_eq!(kind("::1"), IpKind::Loopback);
+    assert_eq!(kind("::"), IpKind::Unspecified);
+    assert_eq!(kind("ff02::1"), IpKind::Multicast);
+    assert_eq!(kind("2606:4700::1111"), IpKind::Uniqeglobal);
+    assert!(IpKind::get_kind("10.0.0").is_err());
+}
+#[test]
+fn special_purpose_test() {
+    assert!(IpKind::is_public("8.8.8.8"));
+    assert!(!IpKind::is_public("192.0.2.1"));
+    assert!(!IpKind::is_public("2001:db8::1"));
+    assert!(!IpKind::is_public("100.64.0.1"));
+    assert!(IpKind::is_public("192.0.0.9"));
+    assert!(!IpKind::is_public("not an address"));
+    assert!(IpKind::is_forwardable("10.0.0.1"));
+    assert!(!IpKind::is_forwardable("169.254.0.1"));
+    assert!(IpKind::is_reserved_by_protocol("127.0.0.1"));
+    assert!(!IpKind::is_reserved_by_protocol("8.8.8.8"));
+    let entry = IpKind::special_purpose("192.0.0.10").unwrap();
+    assert_eq!(entry.network().to_string(), "192.0.0.10/32");
+    assert_eq!(entry.rfc(), "RFC8155");
+    assert!(entry.is_global() && entry.is_forwardable());
+    let entry = IpKind::special_purpose("192.0.0.200").unwrap();
+    assert_eq!(entry.name(), "IETF Protocol Assignments");
+    assert!(!entry.is_source() && !entry.is_destination());
+    let entry = IpKind::special_purpose("2001::1").unwrap();
+    assert_eq!(entry.kind(), &IpKind::Teredo);
+    assert!(IpKind::special_purpose("8.8.8.8").is_none());
+    let entry = IpKind::special_purpose("192.88.99.1").unwrap();
+    assert!(entry.is_deprecated());
+    assert!(
+        !IpKind::special_purpose("192.88.99.2")
+            .unwrap()
+            .is_deprecated()
+    );
+    assert!(
+        IpKind::special_purpose("2001:10::1")
+            .unwrap()
+            .is_deprecated()
+    );
+    assert_eq!(
+        IpKind::get_kind("3fff:1::1").unwrap(),
+        IpKind::Documentation
+    );
+    assert_eq!(IpKind::special_purpose("5f00::1").unwrap().rfc(), "RFC9602");
+    assert_eq!(
+        IpKind::special_purpose("2001:30::1").unwrap().rfc(),
+        "RFC9374"
+    );
+    assert!(!IpKind::is_public("5f00::1"));
+}
 // IpAddress tests
 #[test]
 fn new_ipaddress_test() {
@@ -36,6 +116,24 @@ fn new_ipaddress_test() {
     assert_eq!(addr.to_string(), "192.168.1.1");
     let addr2 = IpAddress::from_str("fc00::1").unwrap();
     assert_eq!(addr2.to_string(), "fc00::1");
+    assert_eq!(IpAddress::from_str("::1").unwrap().to_string(), "::1");
+    assert_eq!(
+        IpAddress::from_str("2001:db8::").unwrap().to_string(),
+        "2001:db8::"
+    );
+    assert_eq!(
+        IpAddress::from_str("1:0:2::").unwrap().to_string(),
+        "1:0:2::"
+    );
+    let addr3 = IpAddress::new(&vec![
+        0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1,
+    ]);
+    assert_eq!(addr3.unwrap().to_string(), "2001:db8::1");
+    assert_eq!(
+        IpAddress::new(&vec![10, 0, 0, 1]).unwrap().to_string(),
+        "10.0.0.1"
+    );
+    assert!(IpAddress::new(&vec![10, 0, 0, 1, 0, 0]).is_err());
 }
 #[test]
 fn expend_shorten_test() {
@@ -55,14 +153,224 @@ fn expend_shorten_test() {
         IpAddress::shorten("0f01:00:0001:0020:0300::0010").unwrap(),
         "f01:0:1:20:300::10".to_string()
     );
+    assert_eq!(IpAddress::shorten("0:0:0:0:0:0:0:1").unwrap(), "::1");
+    assert_eq!(
+        IpAddress::shorten("2001:db8:0:0:0:0:0:0").unwrap(),
+        "2001:db8::"
+    );
+    assert_eq!(
+        IpAddress::shorten("1:0:2:3:4:5:6:7").unwrap(),
+        "1:0:2:3:4:5:6:7"
+    );
+    assert_eq!(IpAddress::shorten("1:0:0:2:0:0:0:3").unwrap(), "1:0:0:2::3");
+}
+#[test]
+fn ipaddress_cmp_test() {
+    let addr1 = IpAddress::from_str("10.0.0.1").unwrap();
+    let addr2 = IpAddress::from_str("10.0.0.1").unwrap();
+    let addr3 = IpAddress::from_str("10.0.1.0").unwrap();
+    let addr4 = IpAddress::from_str("::1").unwrap();
+    assert_eq!(addr1 == addr2, true);
+    assert_eq!(addr1 < addr3, true);
+    assert_eq!(addr3 > addr2, true);
+    assert_eq!(addr3 < addr4, true);
+    let mut addrs = vec![addr4.clone(), addr3.clone(), addr1.clone()];
+    addrs.sort();
+    assert_eq!(addrs, vec![addr1.clone(), addr3, addr4]);
+    let set = std::collections::HashSet::from([addr1, addr2]);
+    assert_eq!(set.len(), 1);
 }
-//#[test]
-//fn ipaddress_cmp_test() {
 
-//}
+#[test]
+fn ipaddress_arithmetic_test() {
+    let addr = IpAddress::from_str("192.168.0.254").unwrap();
+    assert_eq!((&addr + 2).unwrap().to_string(), "192.168.1.0");
+    assert_eq!((&addr - 254).unwrap().to_string(), "192.168.0.0");
+    assert_eq!(addr.next().unwrap().to_string(), "192.168.0.255");
+    assert_eq!(addr.prev().unwrap().to_string(), "192.168.0.253");
+    let other = IpAddress::from_str("192.168.1.4").unwrap();
+    assert_eq!(addr.distance(&other).unwrap(), 6);
+    assert_eq!(other.distance(&addr).unwrap(), -6);
+    assert!(
+        IpAddress::from_str("255.255.255.255")
+            .unwrap()
+            .next()
+            .is_err()
+    );
+    assert!(IpAddress::from_str("0.0.0.0").unwrap().prev().is_err());
+    let err = addr
+        .distance(&IpAddress::from_str("::1").unwrap())
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidIpAddress);
+    let addr6 = IpAddress::from_str("2001:db8::ffff").unwrap();
+    assert_eq!((addr6 + 1).unwrap().to_string(), "2001:db8::1:0");
+    let max6 = IpAddress::from_str("ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff").unwrap();
+    assert!(max6.next().is_err());
+}
 
 // Mask tests
 #[test]
+fn nat64_test() {
+    let addr = IpAddress::from_str("192.0.2.33").unwrap();
+    let cases = [
+        ("2001:db8::/32", "2001:db8:c000:221::"),
+        ("2001:db8:100::/40", "2001:db8:1c0:2:21::"),
+        ("2001:db8:122::/48", "2001:db8:122:c000:2:2100::"),
+        ("2001:db8:122:300::/56", "2001:db8:122:3c0:0:221::"),
+        ("2001:db8:122:344::/64", "2001:db8:122:344:c0:2:2100:0"),
+        ("2001:db8:122:344::/96", "2001:db8:122:344::c000:221"),
+        ("64:ff9b::/96", "64:ff9b::c000:221"),
+    ];
+    for (prefix, expected) in cases {
+        let prefix = Network::from_str(prefix).unwrap();
+        let nat64 = addr.to_nat64(&prefix).unwrap();
+        assert_eq!(nat64.to_string(), expected);
+        assert_eq!(nat64.extract_nat64(&prefix).unwrap(), addr);
+    }
+    let prefix = Network::from_str("2001:db8::/33").unwrap();
+    assert!(addr.to_nat64(&prefix).is_err());
+    let prefix = Network::from_str("2001:db8::/32").unwrap();
+    assert!(
+        IpAddress::from_str("2001:db8:c000:221:ff00::")
+            .unwrap()
+            .extract_nat64(&prefix)
+            .is_err()
+    );
+    assert!(
+        IpAddress::from_str("2001:db9::1")
+            .unwrap()
+            .extract_nat64(&prefix)
+            .is_err()
+    );
+    let prefix = Network::from_str("2001:db8:122:344:ff00::/96").unwrap();
+    assert_eq!(
+        addr.to_nat64(&prefix).unwrap_err().kind(),
+        ErrorKind::InvalidNetwork
+    );
+}
+#[test]
+fn ipv4_embedding_test() {
+    let addr = IpAddress::from_str("192.0.2.33").unwrap();
+    let mapped = addr.to_ipv4_mapped().unwrap();
+    assert_eq!(mapped, IpAddress::from_str("::ffff:192.0.2.33").unwrap());
+    assert_eq!(mapped.extract_ipv4_mapped().unwrap(), addr);
+    assert!(
+        IpAddress::from_str("::1")
+            .unwrap()
+            .extract_ipv4_mapped()
+            .is_err()
+    );
+    assert!(mapped.to_ipv4_mapped().is_err());
+    let six_to_four = addr.to_6to4().unwrap();
+    assert_eq!(six_to_four.to_string(), "2002:c000:221::/48");
+    let host = IpAddress::from_str("2002:c000:221:1::5").unwrap();
+    assert_eq!(host.extract_6to4().unwrap(), addr);
+    assert!(mapped.extract_6to4().is_err());
+    let prefix = Network::from_str("2001:db8:1:2::/64").unwrap();
+    let isatap = addr.to_isatap(&prefix).unwrap();
+    assert_eq!(isatap.to_string(), "2001:db8:1:2:0:5efe:c000:221");
+    assert_eq!(isatap.extract_isatap().unwrap(), addr);
+    let public = IpAddress::from_str("8.8.8.8").unwrap().to_isatap(&prefix);
+    assert_eq!(
+        public.as_ref().unwrap().to_string(),
+        "2001:db8:1:2:200:5efe:808:808"
+    );
+    assert!(
+        addr.to_isatap(&Network::from_str("2001:db8::/48").unwrap())
+            .is_err()
+    );
+    assert!(host.extract_isatap().is_err());
+    let public = public.unwrap();
+    assert_eq!(
+        public.extract_isatap().unwrap(),
+        IpAddress::from_str("8.8.8.8").unwrap()
+    );
+    // the universal/local bit doesn't match the Ipv4 address
+    let local = IpAddress::from_str("2001:db8:1:2:0:5efe:808:808").unwrap();
+    assert!(local.extract_isatap().is_err());
+    let universal = IpAddress::from_str("2001:db8:1:2:200:5efe:c000:221").unwrap();
+    assert!(universal.extract_isatap().is_err());
+    // the group bit is set
+    let group = IpAddress::from_str("2001:db8:1:2:100:5efe:c000:221").unwrap();
+    assert!(group.extract_isatap().is_err());
+}
+#[test]
+fn solicited_node_test() {
+    let addr = IpAddress::from_str("2001:db8::1:800:200e:8c6c").unwrap();
+    assert_eq!(
+        addr.solicited_node().unwrap().to_string(),
+        "ff02::1:ff0e:8c6c"
+    );
+    assert!(
+        IpAddress::from_str("10.0.0.1")
+            .unwrap()
+            .solicited_node()
+            .is_err()
+    );
+}
+#[test]
+fn multicast_mac_test() {
+    let mac = |addr: &str| IpAddress::from_str(addr).unwrap().multicast_mac();
+    assert_eq!(
+        mac("ff02::1:ff0e:8c6c").unwrap().as_bytes(),
+        &[0x33, 0x33, 0xff, 0x0e, 0x8c, 0x6c]
+    );
+    assert_eq!(
+        mac("239.255.0.1").unwrap().as_bytes(),
+        &[0x01, 0x00, 0x5e, 0x7f, 0x00, 0x01]
+    );
+    assert_eq!(
+        mac("224.128.1.2").unwrap().as_bytes(),
+        &[0x01, 0x00, 0x5e, 0x00, 0x01, 0x02]
+    );
+    assert!(mac("10.0.0.1").is_err());
+    assert!(mac("2001:db8::1").is_err());
+}
+#[test]
+fn stable_privacy_test() {
+    let prefix = Network::from_str("2001:db8:1:2::/64").unwrap();
+    let addr = IpAddress::stable_privacy(&prefix, "eth0", "office", 0, b"secret").unwrap();
+    assert_eq!(addr.to_string(), "2001:db8:1:2:ab7:8fa9:f3c5:1191");
+    let again = IpAddress::stable_privacy(&prefix, "eth0", "office", 0, b"secret").unwrap();
+    assert_eq!(addr, again);
+    let retry = IpAddress::stable_privacy(&prefix, "eth0", "office", 1, b"secret").unwrap();
+    assert_eq!(retry.to_string(), "2001:db8:1:2:1119:80fd:17f1:e879");
+    let other = Network::from_str("2001:db8:1:3::/64").unwrap();
+    assert_ne!(
+        IpAddress::stable_privacy(&other, "eth0", "office", 0, b"secret")
+            .unwrap()
+            .as_u128() as u64,
+        addr.as_u128() as u64
+    );
+    let ipv4 = Network::from_str("10.0.0.0/8").unwrap();
+    assert!(IpAddress::stable_privacy(&ipv4, "eth0", "", 0, b"secret").is_err());
+    let temporary = IpAddress::temporary(&prefix, b"secret").unwrap();
+    assert!(prefix.contains(&temporary));
+    assert_ne!(temporary, IpAddress::temporary(&prefix, b"secret").unwrap());
+}
+#[test]
+fn reverse_pointer_test() {
+    let addr = IpAddress::from_str("192.0.2.5").unwrap();
+    assert_eq!(addr.reverse_pointer(), "5.2.0.192.in-addr.arpa");
+    let addr6 = IpAddress::from_str("2001:db8::567:89ab").unwrap();
+    assert_eq!(
+        addr6.reverse_pointer(),
+        "b.a.9.8.7.6.5.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.0.8.b.d.0.1.0.0.2.ip6.arpa"
+    );
+    assert_eq!(
+        IpAddress::from_reverse_pointer("5.2.0.192.in-addr.arpa.").unwrap(),
+        addr
+    );
+    assert_eq!(
+        IpAddress::from_reverse_pointer(&addr6.reverse_pointer().to_uppercase()).unwrap(),
+        addr6
+    );
+    assert!(IpAddress::from_reverse_pointer("2.0.192.in-addr.arpa").is_err());
+    assert!(IpAddress::from_reverse_pointer("5.2.0.300.in-addr.arpa").is_err());
+    assert!(IpAddress::from_reverse_pointer("1.0.ip6.arpa").is_err());
+    assert!(IpAddress::from_reverse_pointer("example.com").is_err());
+}
+#[test]
 fn is_valid_test() {
     assert_eq!(Mask::is_valid("255.255.255.0"), true);
     assert_eq!(Mask::is_valid("230.0.0.0"), false);
@@ -81,6 +389,52 @@ fn new_mask_test() {
     assert_eq!(mask2.to_string(), "255.255.255.0");
     assert_eq!(mask2.wildcard(), "0.0.0.255");
 }
+#[test]
+fn new_mask_v6_test() {
+    let mask = Mask::from_prefix_v6(64).unwrap();
+    assert_eq!(mask.to_string(), "ffff:ffff:ffff:ffff::");
+    assert_eq!(mask.wildcard(), "::ffff:ffff:ffff:ffff");
+    assert_eq!(*mask.num_of_hosts(), 1u128 << 64);
+    assert_eq!(*mask.version(), IpVersion::V6);
+    let mask2 = Mask::from_str("ffff:ffff:ffff::").unwrap();
+    assert_eq!(*mask2.prefix(), 48);
+    assert_eq!(Mask::is_valid("ffff:ff00::"), true);
+    assert_eq!(Mask::is_valid("ffff:00ff::"), false);
+    assert_eq!(*Mask::from_prefix_v6(128).unwrap().num_of_hosts(), 1);
+    assert!(Mask::from_prefix_v6(129).is_err());
+    assert!(Mask::from_prefix(33).is_err());
+    assert_eq!(*Mask::from_prefix(32).unwrap().num_of_hosts(), 0);
+    assert_eq!(
+        *Mask::from_prefix(0).unwrap().num_of_hosts(),
+        (1u128 << 32) - 2
+    );
+}
+#[test]
+fn wildcard_mask_test() {
+    let addr = |addr: &str| IpAddress::from_str(addr).unwrap();
+    let wildcard = WildcardMask::from_str("0.0.0.255").unwrap();
+    assert!(wildcard.is_contiguous());
+    assert_eq!(wildcard.to_mask().unwrap(), Mask::from_prefix(24).unwrap());
+    assert_eq!(wildcard.num_of_addresses(), 256);
+    assert_eq!(
+        WildcardMask::from(&Mask::from_str("255.240.0.0").unwrap()).to_string(),
+        "0.15.255.255"
+    );
+    let sparse = WildcardMask::from_str("0.0.255.0").unwrap();
+    assert!(!sparse.is_contiguous());
+    assert!(sparse.to_mask().is_err());
+    assert_eq!(sparse.to_string(), "0.0.255.0");
+    assert!(sparse.matches(&addr("10.1.0.5"), &addr("10.1.77.5")));
+    assert!(!sparse.matches(&addr("10.1.0.5"), &addr("10.1.77.6")));
+    assert!(!sparse.matches(&addr("10.1.0.5"), &addr("::1")));
+    let any = WildcardMask::from_str("255.255.255.255").unwrap();
+    assert_eq!(any.to_mask().unwrap(), Mask::from_prefix(0).unwrap());
+    assert!(any.matches(&addr("0.0.0.0"), &addr("8.8.8.8")));
+    let v6 = WildcardMask::from_str("::ffff:ffff:ffff:ffff").unwrap();
+    assert_eq!(v6.to_mask().unwrap(), Mask::from_prefix_v6(64).unwrap());
+    assert!(WildcardMask::new(&vec![0, 0, 255]).is_err());
+    assert!(!WildcardMask::is_valid("0.0.256.0"));
+}
 // Network tests
 #[test]
 fn new_network_test() {
@@ -110,6 +464,48 @@ fn new_network_test() {
     assert_eq!("10.31.255.255", super_big_net.broadcast().address())
 }
 
+#[test]
+fn new_network_v6_test() {
+    let net = Network::from_str("2001:db8::/32").unwrap();
+    assert_eq!(net.to_string(), "2001:db8::/32");
+    assert_eq!(net.netid().to_string(), "2001:db8::");
+    assert_eq!(*net.mask().prefix(), 32);
+    assert!(net.try_broadcast().is_none());
+    assert_eq!(
+        net.broadcast().to_string(),
+        "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff"
+    );
+    let net2 = Network::new(
+        IpAddress::from_str("fd00:1:2:3::").unwrap(),
+        Mask::from_prefix_v6(64).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(net2.to_string(), "fd00:1:2:3::/64");
+    assert_eq!(Network::from_str("::/0").unwrap().to_string(), "::/0");
+    assert!(Network::from_str("2001:db8::1/32").is_err());
+    assert!(Network::from_str("2001:db8::/129").is_err());
+    assert!(Network::from_str("10.0.17.0/20").is_err());
+    assert!(
+        Network::new(
+            IpAddress::from_str("10.0.0.0").unwrap(),
+            Mask::from_prefix_v6(8).unwrap()
+        )
+        .is_err()
+    );
+    let subnets = net
+        .subnets(48)
+        .unwrap()
+        .take(2)
+        .map(|net| net.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(subnets, vec!["2001:db8::/48", "2001:db8:1::/48"]);
+    let summary = Network::summarize(&[
+        Network::from_str("2001:db8:0:1::/64").unwrap(),
+        Network::from_str("2001:db8::/64").unwrap(),
+    ]);
+    assert_eq!(summary[0].to_string(), "2001:db8::/63");
+}
+
 #[test]
 fn containes_test() {
     let net1 = Network::from_str("192.168.15.128/28").unwrap();
@@ -117,6 +513,38 @@ fn containes_test() {
     let addr = IpAddress::from_str("10.1.12.2").unwrap();
     assert_eq!(net1.contains(&addr), false);
     assert_eq!(net2.contains(&addr), true);
+    let net3 = Network::from_str("10.0.0.128/28").unwrap();
+    assert_eq!(
+        net3.contains(&IpAddress::from_str("192.168.0.130").unwrap()),
+        false
+    );
+    let net6 = Network::from_str("2001:db8:abcd::/48").unwrap();
+    assert_eq!(
+        net6.contains(&IpAddress::from_str("2001:db8:abcd:12::1").unwrap()),
+        true
+    );
+    assert_eq!(
+        net6.contains(&IpAddress::from_str("2001:db8:abce::1").unwrap()),
+        false
+    );
+    assert_eq!(net6.contains(&addr), false);
+    // the Ipv4 net id and broadcast are in the network, not only the hosts
+    assert_eq!(net2.contains(net2.netid()), true);
+    assert_eq!(net2.contains(net2.broadcast()), true);
+    let host = Network::from_str("10.1.12.7/32").unwrap();
+    assert_eq!(
+        host.contains(&IpAddress::from_str("10.1.12.7").unwrap()),
+        true
+    );
+    let link = Network::from_str("10.1.12.6/31").unwrap();
+    assert_eq!(
+        link.contains(&IpAddress::from_str("10.1.12.6").unwrap()),
+        true
+    );
+    assert_eq!(
+        link.contains(&IpAddress::from_str("10.1.12.7").unwrap()),
+        true
+    );
 }
 
 #[test]
@@ -131,6 +559,143 @@ fn new_mac_test() {
     assert_eq!(mac4.is_err(), true);
 }
 
+#[test]
+fn mac_notation_test() {
+    let mac = MacAddress::from_str("ac:12:00:1f:ff:22").unwrap();
+    for notation in [
+        "AC-12-00-1F-FF-22",
+        "ac12001fff22",
+        "ac12.001f.ff22",
+        "AC:12:00:FF:FE:1F:FF:22",
+        "ac1200fffe1fff22",
+    ] {
+        assert_eq!(MacAddress::from_str(notation).unwrap(), mac);
+    }
+    assert_eq!(mac.format(&MacNotation::Colon), "AC:12:00:1F:FF:22");
+    assert_eq!(mac.format(&MacNotation::Dash), "AC-12-00-1F-FF-22");
+    assert_eq!(mac.format(&MacNotation::Bare), "AC12001FFF22");
+    assert_eq!(mac.format(&MacNotation::Cisco), "AC12.001F.FF22");
+    assert_eq!(mac.format(&MacNotation::Eui64), "AC:12:00:FF:FE:1F:FF:22");
+    assert!(MacAddress::from_str("AC:12:00:11:22:1F:FF:22").is_err());
+    assert!(MacAddress::from_str("ac12.001f").is_err());
+    assert!(MacAddress::from_str("ac12001fff2").is_err());
+    assert!(MacAddress::from_str("AC:12-00:1F:FF:22").is_err());
+}
+#[test]
+fn mac_parse_all_test() {
+    let macs = MacAddress::parse_all(&["54:ee:75:b0:11:34", "not a mac", "54ee.75b0.1135"]);
+    assert_eq!(macs.len(), 3);
+    assert!(macs[1].is_err());
+    let first = macs[0].as_ref().unwrap();
+    assert_eq!(first.as_bytes(), &[0x54, 0xee, 0x75, 0xb0, 0x11, 0x34]);
+    assert_eq!(first.vendor(), macs[2].as_ref().unwrap().vendor());
+    assert_eq!(first.vendor(), "Wistron InfoComm(Kunshan)Co.,Ltd.");
+    assert!(MacAddress::parse_all::<&str>(&[]).is_empty());
+}
+#[test]
+fn oui_database_test() {
+    let manuf = "# comment\n00:00:0C\tCisco\tCisco Systems, Inc\n70:B3:D5\t\tIEEE Registration Authority\n70:B3:D5:10:00:00/28\tLab Inc.\n";
+    let mut database = OuiDatabase::parse_manuf(manuf).unwrap();
+    assert_eq!(database.len(), 3);
+    let lookup = |database: &OuiDatabase, address: &str| {
+        database
+            .lookup(&MacAddress::from_str(address).unwrap())
+            .map(|oui| oui.vendor().to_owned())
+    };
+    assert_eq!(
+        lookup(&database, "00:00:0c:12:34:56").unwrap(),
+        "Cisco Systems, Inc"
+    );
+    assert_eq!(lookup(&database, "70:b3:d5:1f:00:01").unwrap(), "Lab Inc.");
+    assert_eq!(
+        lookup(&database, "70:b3:d5:20:00:01").unwrap(),
+        "IEEE Registration Authority"
+    );
+    assert_eq!(lookup(&database, "00:00:0d:00:00:00"), None);
+    let csv = "Registry,Assignment,Organization Name,Organization Address\n\
+        MA-L,00000C,Cisco Systems Inc,San Jose\n\
+        MA-S,70B3D5F01,\"Tiny, Ltd\",Somewhere\n";
+    database.extend(OuiDatabase::parse_csv(csv).unwrap());
+    assert_eq!(
+        lookup(&database, "00:00:0c:12:34:56").unwrap(),
+        "Cisco Systems Inc"
+    );
+    assert_eq!(lookup(&database, "70:b3:d5:f0:10:00").unwrap(), "Tiny, Ltd");
+    let ouis = database.ouis_for_vendor("cisco|tiny");
+    assert_eq!(
+        ouis.iter()
+            .map(|oui| oui.to_string())
+            .collect::<Vec<String>>(),
+        vec!["00:00:0C", "70:B3:D5:F0:10:00/36"]
+    );
+    assert!(ouis[1].contains(&MacAddress::from_str("70:b3:d5:f0:1f:ff").unwrap()));
+    assert!(!ouis[1].contains(&MacAddress::from_str("70:b3:d5:f0:20:00").unwrap()));
+    assert!(database.ouis_for_vendor("((").is_empty());
+    let oui = Oui::new(
+        &MacAddress::from_str("02:00:00:ff:ff:ff").unwrap(),
+        28,
+        "Lab",
+    )
+    .unwrap();
+    assert_eq!(
+        oui.prefix(),
+        MacAddress::from_str("02:00:00:f0:00:00").unwrap()
+    );
+    assert_eq!(database.insert(oui), None);
+    assert!(
+        Oui::new(
+            &MacAddress::from_str("02:00:00:00:00:00").unwrap(),
+            49,
+            "Lab"
+        )
+        .is_err()
+    );
+    assert!(OuiDatabase::parse_manuf("00:00:0C").is_err());
+    assert!(OuiDatabase::parse_manuf("0G:00:0C\tBad").is_err());
+    assert!(OuiDatabase::parse_csv("Registry,Name\nMA-L,Bad").is_err());
+}
+#[test]
+fn vendor_database_test() {
+    // a database of its own so the shared one the other tests use is left untouched
+    let mut database = OuiDatabase::builtin();
+    let cisco = database.ouis_for_vendor("^cisco systems");
+    assert!(cisco.len() > 100);
+    assert!(
+        cisco
+            .iter()
+            .all(|oui| oui.vendor().starts_with("Cisco Systems"))
+    );
+    let mac = MacAddress::from_str("06:00:00:12:34:56").unwrap();
+    assert!(database.lookup(&mac).is_none());
+    assert_eq!(mac.vendor(), "Unknown");
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("vendor_database_test");
+    std::fs::write(&path, "06:00:00\tYrnu Test Vendor\n").unwrap();
+    database.extend(OuiDatabase::load(&path).unwrap());
+    assert_eq!(database.lookup(&mac).unwrap().vendor(), "Yrnu Test Vendor");
+    assert_eq!(database.ouis_for_vendor("yrnu test").len(), 1);
+    std::fs::remove_file(&path).unwrap();
+    assert!(OuiDatabase::load(&path).is_err());
+    assert!(MacAddress::load_vendor_database(&path).is_err());
+}
+#[test]
+fn mac_flags_test() {
+    let universal = MacAddress::from_str("00:1A:2B:3C:4D:5E").unwrap();
+    assert!(universal.is_unicast() && universal.is_universal());
+    let multicast = MacAddress::from_str("01:00:5E:00:00:01").unwrap();
+    assert!(multicast.is_multicast() && !multicast.is_broadcast());
+    let local = MacAddress::from_str("02:00:00:00:00:01").unwrap();
+    assert!(local.is_local() && local.is_unicast());
+    assert!(
+        MacAddress::from_str("FF:FF:FF:FF:FF:FF")
+            .unwrap()
+            .is_broadcast()
+    );
+    let random = MacAddress::random(None).unwrap();
+    assert!(random.is_local() && random.is_unicast());
+    assert_ne!(random, MacAddress::random(None).unwrap());
+    let vendor = MacAddress::random(Some([0x00, 0x1a, 0x2b])).unwrap();
+    assert_eq!(&vendor.as_bytes()[..3], &[0x00, 0x1a, 0x2b]);
+}
 #[test]
 fn mac_cmp_test() {
     let mac1 = MacAddress::from_str("AB:CD:EF:12:34:56").unwrap();
@@ -175,3 +740,1128 @@ fn new_if_test() {
         //        println!("\n\n{}",inf);
     }
 }
+
+#[test]
+fn interface_details_test() {
+    if !std::path::Path::new("/sys/class/net/lo").exists() {
+        return;
+    }
+    let lo = Interface::by_name("lo").unwrap();
+    assert!(lo.is_loopback() && lo.is_up());
+    assert!(lo.mtu().is_some());
+    assert_ne!(lo.state(), &InterfaceState::Down);
+    let addresses = lo
+        .addresses()
+        .iter()
+        .map(|address| address.to_string())
+        .collect::<Vec<String>>();
+    assert!(addresses.contains(&String::from("127.0.0.1/8")));
+    let address = &lo.addresses()[0];
+    assert_eq!(address.network(), Network::from_str("127.0.0.0/8").unwrap());
+    assert_eq!(lo.ipv4().as_ref(), Some(address.address()));
+    let counters = lo.counters().as_ref().unwrap();
+    assert!(counters.rx_packets() <= counters.rx_bytes());
+    assert_eq!(Interface::by_index(*lo.index()).unwrap().name(), "lo");
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+#[ignore = "needs the privileges to create a network namespace, run with --ignored as root"]
+fn interface_watcher_test() {
+    use yrnu::core::{InterfaceEvent, InterfaceWatcher};
+    // Moves the test thread into a new network namespace
+    assert_eq!(unsafe { libc::unshare(libc::CLONE_NEWNET) }, 0);
+    let mut watcher = InterfaceWatcher::new().unwrap();
+    let ip = |args: &[&str]| {
+        std::process::Command::new("ip")
+            .args(args)
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+    assert!(ip(&["link", "set", "lo", "up"]));
+    assert!(ip(&["addr", "add", "10.9.9.1/24", "dev", "lo"]));
+    let timeout = Some(std::time::Duration::from_secs(2));
+    let mut events = vec![];
+    while let Some(event) = watcher.next_event(timeout).unwrap() {
+        events.push(event);
+    }
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, InterfaceEvent::LinkUp(_)))
+            .count(),
+        1
+    );
+    assert!(events.iter().any(
+        |event| matches!(event, InterfaceEvent::LinkUp(interface) if interface.name() == "lo")
+    ));
+    let added = events
+        .iter()
+        .find(|event| event.kind() == "address_added" && event.to_string().contains("10.9.9.1"))
+        .unwrap();
+    assert_eq!(added.address().unwrap().to_string(), "10.9.9.1/24");
+    assert_eq!(added.interface().name(), "lo");
+    // The interface of an event is kept up to date from the notifications
+    assert!(
+        added
+            .interface()
+            .addresses()
+            .contains(added.address().unwrap())
+    );
+    assert!(ip(&["addr", "del", "10.9.9.1/24", "dev", "lo"]));
+    let removed = watcher.next_event(timeout).unwrap().unwrap();
+    assert_eq!(removed.to_string(), "address_removed lo 10.9.9.1/24");
+    assert!(
+        !removed
+            .interface()
+            .addresses()
+            .contains(removed.address().unwrap())
+    );
+    // A link change that keeps the link up and running is not an event
+    assert!(ip(&["link", "set", "lo", "mtu", "1500"]));
+    assert!(watcher.next_event(timeout).unwrap().is_none());
+    assert!(ip(&["link", "set", "lo", "down"]));
+    let mut events = vec![];
+    while let Some(event) = watcher.next_event(timeout).unwrap() {
+        events.push(event);
+    }
+    let down = events
+        .iter()
+        .find(|event| event.to_string() == "link_down lo")
+        .unwrap();
+    assert_eq!(down.interface().mtu(), &Some(1500));
+    assert!(!down.interface().is_up());
+}
+
+#[test]
+fn route_test() {
+    let ipv4 = "Iface\tDestination\tGateway \tFlags\tRefCnt\tUse\tMetric\tMask\t\tMTU\tWindow\tIRTT
+eth0\t00000000\t010200C0\t0003\t0\t0\t100\t00000000\t0\t0\t0
+eth0\t000200C0\t00000000\t0001\t0\t0\t100\t00FFFFFF\t0\t0\t0
+wg0\t000200C0\t00000000\t0001\t0\t0\t50\t00FFFFFF\t0\t0\t0
+eth1\t0000000A\t00000000\t0001\t0\t0\t0\t000000FF\t0\t0\t0
+eth1\t0000010A\t00000000\t0200\t0\t0\t0\t0000FFFF\t0\t0\t0";
+    let ipv6 = "fd000000000000000000000000000000 40 00000000000000000000000000000000 00 00000000000000000000000000000000 00000100 00000001 00000000 00000001     eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 fd000000000000000000000000000001 00000400 00000001 00000000 00000003     eth0
+00000000000000000000000000000000 00 00000000000000000000000000000000 00 00000000000000000000000000000000 ffffffff 00000001 00000000 00200200       lo";
+    let mut routes = Route::parse_ipv4_table(ipv4);
+    assert_eq!(routes.len(), 4);
+    routes.extend(Route::parse_ipv6_table(ipv6));
+    assert_eq!(routes.len(), 6);
+    let route_for = |address: &str| {
+        Route::lookup(&routes, &IpAddress::from_str(address).unwrap())
+            .map(|route| route.to_string())
+    };
+    assert_eq!(
+        route_for("8.8.8.8").unwrap(),
+        "0.0.0.0/0 via 192.0.2.1 dev eth0 metric 100"
+    );
+    assert_eq!(
+        route_for("192.0.2.9").unwrap(),
+        "192.0.2.0/24 dev wg0 metric 50"
+    );
+    assert_eq!(
+        route_for("10.1.2.3").unwrap(),
+        "10.0.0.0/8 dev eth1 metric 0"
+    );
+    assert_eq!(
+        route_for("fd00::9").unwrap(),
+        "fd00::/64 dev eth0 metric 256"
+    );
+    let default = Route::lookup(&routes, &IpAddress::from_str("2001:db8::1").unwrap()).unwrap();
+    assert!(default.is_default());
+    assert_eq!(default.gateway().as_ref().unwrap().to_string(), "fd00::1");
+    assert_eq!(default.interface_name(), "eth0");
+    assert_eq!(default.metric(), 1024);
+    assert_eq!(
+        Route::lookup(&routes[1..4], &IpAddress::from_str("8.8.8.8").unwrap()),
+        None
+    );
+    if std::path::Path::new("/proc/net/route").exists() {
+        let routes = Route::all();
+        for route in &routes {
+            let found = Route::lookup(&routes, route.destination().netid()).unwrap();
+            assert!(found.destination().mask().prefix() >= route.destination().mask().prefix());
+        }
+    }
+}
+
+#[test]
+fn neighbor_test() {
+    let arp = "IP address       HW type     Flags       HW address            Mask     Device
+192.0.2.1        0x1         0x2         54:ee:75:b0:11:34     *        eth0
+192.0.2.7        0x1         0x0         00:00:00:00:00:00     *        eth0
+192.0.2.9        0x1         0x6         02:00:00:00:00:09     *        wlan0";
+    let neighbors = Neighbor::parse_arp_table(arp);
+    assert_eq!(neighbors.len(), 3);
+    assert_eq!(neighbors[0].state(), &NeighborState::Complete);
+    assert_eq!(
+        neighbors[0].vendor().as_deref(),
+        Some("Wistron InfoComm(Kunshan)Co.,Ltd.")
+    );
+    assert_eq!(
+        neighbors[0].to_string(),
+        "192.0.2.1 dev eth0 lladdr 54:EE:75:B0:11:34 COMPLETE"
+    );
+    assert_eq!(neighbors[1].mac(), &None);
+    assert_eq!(neighbors[1].vendor(), &None);
+    assert_eq!(neighbors[1].state(), &NeighborState::Incomplete);
+    assert_eq!(neighbors[2].state(), &NeighborState::Permanent);
+    assert_eq!(neighbors[2].interface_name(), "wlan0");
+    assert_eq!(NeighborState::from(0x04), NeighborState::Stale);
+    assert_eq!(NeighborState::from(0x20).to_string(), "FAILED");
+    for neighbor in Neighbor::all() {
+        assert_eq!(neighbor.vendor().is_some(), neighbor.mac().is_some());
+    }
+}
+
+#[test]
+fn network_relations_test() {
+    let net = |net: &str| Network::from_str(net).unwrap();
+    let big = net("10.0.0.0/8");
+    let small = net("10.20.0.0/16");
+    assert!(big.overlaps(&small));
+    assert!(small.overlaps(&big));
+    assert!(!small.overlaps(&net("10.21.0.0/16")));
+    assert!(!big.overlaps(&net("::/0")));
+    assert!(small.is_subnet_of(&big));
+    assert!(!big.is_subnet_of(&small));
+    assert!(big.is_supernet_of(&small));
+    assert!(big.is_subnet_of(&big) && big.is_supernet_of(&big));
+    assert!(!net("192.168.0.0/24").is_subnet_of(&big));
+}
+#[test]
+fn exclude_test() {
+    let net = |net: &str| Network::from_str(net).unwrap();
+    assert_eq!(
+        net("192.168.0.0/24").exclude(&net("192.168.0.64/26")),
+        vec![net("192.168.0.0/26"), net("192.168.0.128/25")]
+    );
+    assert_eq!(
+        net("10.0.0.0/29").exclude(&net("10.0.0.5/32")),
+        vec![net("10.0.0.0/30"), net("10.0.0.4/32"), net("10.0.0.6/31")]
+    );
+    assert_eq!(
+        net("2001:db8::/32").exclude(&net("2001:db8::/33")),
+        vec![net("2001:db8:8000::/33")]
+    );
+    let other = net("172.16.0.0/12");
+    assert_eq!(net("10.0.0.0/8").exclude(&other), vec![net("10.0.0.0/8")]);
+    assert!(net("10.1.0.0/16").exclude(&net("10.0.0.0/8")).is_empty());
+}
+#[test]
+fn reverse_zones_test() {
+    let zones = |net: &str| Network::from_str(net).unwrap().reverse_zones();
+    assert_eq!(zones("192.168.1.0/24"), vec!["1.168.192.in-addr.arpa"]);
+    assert_eq!(zones("10.0.0.0/8"), vec!["10.in-addr.arpa"]);
+    assert_eq!(zones("0.0.0.0/0"), vec!["in-addr.arpa"]);
+    assert_eq!(
+        zones("172.16.4.0/22"),
+        vec![
+            "4.16.172.in-addr.arpa",
+            "5.16.172.in-addr.arpa",
+            "6.16.172.in-addr.arpa",
+            "7.16.172.in-addr.arpa"
+        ]
+    );
+    assert_eq!(zones("192.0.2.64/26"), vec!["64/26.2.0.192.in-addr.arpa"]);
+    assert_eq!(zones("192.0.2.7/32"), vec!["7.2.0.192.in-addr.arpa"]);
+    assert_eq!(zones("2001:db8::/32"), vec!["8.b.d.0.1.0.0.2.ip6.arpa"]);
+    assert_eq!(
+        zones("2001:db8::/31"),
+        vec!["8.b.d.0.1.0.0.2.ip6.arpa", "9.b.d.0.1.0.0.2.ip6.arpa"]
+    );
+}
+#[test]
+fn subnets_test() {
+    let net = Network::from_str("192.168.1.0/24").unwrap();
+    let subnets = net
+        .subnets(26)
+        .unwrap()
+        .map(|net| net.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        subnets,
+        vec![
+            "192.168.1.0/26",
+            "192.168.1.64/26",
+            "192.168.1.128/26",
+            "192.168.1.192/26"
+        ]
+    );
+    assert_eq!(net.subnets(24).unwrap().count(), 1);
+    assert_eq!(net.subnets(32).unwrap().count(), 256);
+    assert!(net.subnets(23).is_err());
+    assert!(net.subnets(33).is_err());
+    let big_net = Network::from_str("10.0.0.0/8").unwrap();
+    let last = big_net.subnets(16).unwrap().last().unwrap();
+    assert_eq!(last.to_string(), "10.255.0.0/16");
+    assert_eq!(last.broadcast().address(), "10.255.255.255");
+}
+
+#[test]
+fn split_test() {
+    let net = Network::from_str("10.0.0.0/16").unwrap();
+    let halves = net.split(2).unwrap();
+    assert_eq!(halves[0].to_string(), "10.0.0.0/17");
+    assert_eq!(halves[1].to_string(), "10.0.128.0/17");
+    assert_eq!(net.split(3).unwrap().len(), 4);
+    assert_eq!(net.split(1).unwrap()[0], net);
+    assert!(net.split(0).is_err());
+    let err = net.split(u32::MAX).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InsufficientAddressSpace);
+    assert_eq!(
+        Network::from_str("10.0.0.0/30")
+            .unwrap()
+            .split(8)
+            .unwrap_err()
+            .kind(),
+        ErrorKind::InsufficientAddressSpace
+    );
+}
+
+#[test]
+fn vlsm_test() {
+    let net = Network::from_str("192.168.10.0/24").unwrap();
+    let subnets = net
+        .vlsm(&[10, 50, 2, 100])
+        .unwrap()
+        .iter()
+        .map(|net| net.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        subnets,
+        vec![
+            "192.168.10.192/28",
+            "192.168.10.128/26",
+            "192.168.10.208/30",
+            "192.168.10.0/25"
+        ]
+    );
+    assert!(net.vlsm(&[200, 60]).is_err());
+    assert!(net.vlsm(&[300]).is_err());
+    assert_eq!(net.vlsm(&[126, 126]).unwrap().len(), 2);
+}
+
+#[test]
+fn summarize_test() {
+    let networks = [
+        "10.0.1.0/24",
+        "10.0.0.0/24",
+        "10.0.2.0/23",
+        "10.0.2.128/25",
+        "192.168.0.0/25",
+        "192.168.0.128/26",
+    ]
+    .iter()
+    .map(|net| Network::from_str(net).unwrap())
+    .collect::<Vec<Network>>();
+    let summary = Network::summarize(&networks)
+        .iter()
+        .map(|net| net.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        summary,
+        vec!["10.0.0.0/22", "192.168.0.0/25", "192.168.0.128/26"]
+    );
+    assert!(Network::summarize(&[]).is_empty());
+}
+
+#[test]
+fn supernet_test() {
+    let networks = ["172.16.4.0/24", "172.16.5.0/24", "172.16.7.128/25"]
+        .iter()
+        .map(|net| Network::from_str(net).unwrap())
+        .collect::<Vec<Network>>();
+    assert_eq!(
+        Network::supernet(&networks).unwrap().to_string(),
+        "172.16.4.0/22"
+    );
+    let net = Network::from_str("10.1.2.0/24").unwrap();
+    assert_eq!(Network::supernet(&[net.clone()]).unwrap(), net);
+    assert!(Network::supernet(&[]).is_err());
+}
+
+#[test]
+fn hosts_test() {
+    let net = Network::from_str("192.168.1.0/29").unwrap();
+    let hosts = net
+        .hosts()
+        .map(|addr| addr.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(hosts.len(), 6);
+    assert_eq!(hosts[0], "192.168.1.1");
+    assert_eq!(hosts[5], "192.168.1.6");
+    assert_eq!(net.addresses().count(), 8);
+    assert_eq!(net.size(), 8);
+    assert_eq!(net.first_host().to_string(), "192.168.1.1");
+    assert_eq!(net.last_host().to_string(), "192.168.1.6");
+    let p2p = Network::from_str("10.0.0.2/31").unwrap();
+    assert_eq!(p2p.hosts().count(), 2);
+    assert_eq!(p2p.first_host().to_string(), "10.0.0.2");
+    let big_net = Network::from_str("10.0.0.0/8").unwrap();
+    assert_eq!(big_net.hosts().nth(300).unwrap().to_string(), "10.0.1.45");
+}
+
+#[test]
+fn nth_test() {
+    let net = Network::from_str("10.1.0.0/16").unwrap();
+    assert_eq!(net.nth(0).unwrap().to_string(), "10.1.0.0");
+    assert_eq!(net.nth(257).unwrap().to_string(), "10.1.1.1");
+    assert_eq!(net.nth(65535).unwrap().to_string(), "10.1.255.255");
+    assert_eq!(net.nth(-1).unwrap().to_string(), "10.1.255.255");
+    assert_eq!(net.nth(-2).unwrap().to_string(), "10.1.255.254");
+    assert_eq!(net.nth(-65536).unwrap().to_string(), "10.1.0.0");
+    assert!(net.nth(65536).is_none());
+    assert!(net.nth(-65537).is_none());
+}
+
+#[test]
+fn ip_range_test() {
+    let range = IpRange::from_str("10.0.0.5-10.0.0.77").unwrap();
+    assert_eq!(range.to_string(), "10.0.0.5-10.0.0.77");
+    assert_eq!(range.size(), 73);
+    assert_eq!(range.addresses().count(), 73);
+    assert!(range.contains(&IpAddress::from_str("10.0.0.5").unwrap()));
+    assert!(range.contains(&IpAddress::from_str("10.0.0.77").unwrap()));
+    assert!(!range.contains(&IpAddress::from_str("10.0.0.78").unwrap()));
+    assert!(!range.contains(&IpAddress::from_str("::1").unwrap()));
+    let networks = range
+        .to_networks()
+        .iter()
+        .map(|net| net.to_string())
+        .collect::<Vec<String>>();
+    assert_eq!(
+        networks,
+        vec![
+            "10.0.0.5/32",
+            "10.0.0.6/31",
+            "10.0.0.8/29",
+            "10.0.0.16/28",
+            "10.0.0.32/27",
+            "10.0.0.64/29",
+            "10.0.0.72/30",
+            "10.0.0.76/31"
+        ]
+    );
+    let net = Network::from_str("192.168.0.0/16").unwrap();
+    let net_range = IpRange::from(&net);
+    assert_eq!(net_range.to_string(), "192.168.0.0-192.168.255.255");
+    assert_eq!(net_range.to_networks(), vec![net]);
+    let range6 = IpRange::from_str("2001:db8:: - 2001:db8::ff").unwrap();
+    assert_eq!(range6.to_networks()[0].to_string(), "2001:db8::/120");
+    assert!(IpRange::from_str("10.0.0.9-10.0.0.1").is_err());
+    assert!(IpRange::from_str("10.0.0.1-::1").is_err());
+    assert!(IpRange::from_str("10.0.0.1").is_err());
+}
+
+#[test]
+fn ip_set_test() {
+    let net = |net: &str| Network::from_str(net).unwrap();
+    let addr = |addr: &str| IpAddress::from_str(addr).unwrap();
+    let mut set: IpSet<&str> = IpSet::new();
+    assert!(set.is_empty());
+    set.insert(&net("10.0.0.0/8"), "corp");
+    set.insert(&net("10.1.0.0/16"), "lab");
+    set.insert(&net("2001:db8::/32"), "v6");
+    assert_eq!(set.insert(&net("10.1.0.0/16"), "lab2"), Some("lab"));
+    set.insert_address(&addr("192.168.1.1"), "gateway");
+    assert_eq!(set.len(), 4);
+    let (matched, value) = set.longest_match(&addr("10.1.2.3")).unwrap();
+    assert_eq!(
+        (matched.to_string(), *value),
+        ("10.1.0.0/16".to_string(), "lab2")
+    );
+    assert_eq!(*set.longest_match(&addr("10.2.0.1")).unwrap().1, "corp");
+    assert_eq!(*set.longest_match(&addr("2001:db8::1")).unwrap().1, "v6");
+    assert!(set.longest_match(&addr("192.168.1.2")).is_none());
+    assert!(set.contains(&addr("192.168.1.1")));
+    assert!(!set.contains(&addr("::1")));
+    assert_eq!(set.get(&net("10.0.0.0/8")), Some(&"corp"));
+    assert_eq!(set.get(&net("10.0.0.0/9")), None);
+    set.remove(&net("10.0.0.0/9"));
+    set.remove_address(&addr("192.168.1.1"));
+    assert_eq!(
+        set.networks(),
+        vec![net("10.128.0.0/9"), net("2001:db8::/32")]
+    );
+    assert_eq!(*set.longest_match(&addr("10.200.0.1")).unwrap().1, "corp");
+    assert!(!set.contains(&addr("10.1.2.3")));
+    set.remove(&net("10.130.0.0/16"));
+    assert!(!set.contains(&addr("10.130.0.1")));
+    assert!(set.contains(&addr("10.131.0.1")));
+    assert_eq!(set.len(), 8);
+}
+#[test]
+fn ip_set_algebra_test() {
+    let net = |net: &str| Network::from_str(net).unwrap();
+    let a: IpSet = ["10.0.0.0/8", "192.168.1.0/24", "2001:db8::/32"]
+        .iter()
+        .map(|n| net(n))
+        .collect();
+    let b: IpSet = ["10.64.0.0/10", "192.168.0.0/16", "172.16.0.0/12"]
+        .iter()
+        .map(|n| net(n))
+        .collect();
+    assert_eq!(
+        a.union(&b).networks(),
+        vec![
+            net("10.0.0.0/8"),
+            net("10.64.0.0/10"),
+            net("172.16.0.0/12"),
+            net("192.168.0.0/16"),
+            net("192.168.1.0/24"),
+            net("2001:db8::/32")
+        ]
+    );
+    assert_eq!(
+        a.intersection(&b).networks(),
+        vec![net("10.64.0.0/10"), net("192.168.1.0/24")]
+    );
+    assert_eq!(
+        a.difference(&b).networks(),
+        vec![
+            net("10.0.0.0/10"),
+            net("10.128.0.0/9"),
+            net("2001:db8::/32")
+        ]
+    );
+    assert_eq!(
+        b.difference(&a).networks(),
+        vec![
+            net("172.16.0.0/12"),
+            net("192.168.0.0/24"),
+            net("192.168.2.0/23"),
+            net("192.168.4.0/22"),
+            net("192.168.8.0/21"),
+            net("192.168.16.0/20"),
+            net("192.168.32.0/19"),
+            net("192.168.64.0/18"),
+            net("192.168.128.0/17")
+        ]
+    );
+    assert!(a.difference(&a).is_empty());
+    assert!(a.intersection(&IpSet::<()>::new()).is_empty());
+}
+
+#[test]
+fn service_test() {
+    let ssh = Service::by_name("ssh", Some(Protocol::Tcp)).unwrap();
+    assert_eq!(ssh.port(), port::SSH);
+    assert_eq!(ssh.to_string(), "ssh 22/tcp");
+    assert_eq!(Service::by_name("DNS", None).unwrap().name(), "domain");
+    assert_eq!(
+        Service::by_port(514, Some(Protocol::Udp)).unwrap().name(),
+        "syslog"
+    );
+    assert_eq!(
+        Service::by_port(514, Some(Protocol::Tcp)).unwrap().name(),
+        "shell"
+    );
+    assert!(Service::by_name("router", Some(Protocol::Tcp)).is_none());
+    let services = Services::parse(
+        "# comment line
+myapp\t\t7777/tcp\tmy-app app # My application
+broken\t\t7778
+myapp\t\t7777/udp
+bad\t\t99999/tcp",
+    );
+    assert_eq!(services.len(), 2);
+    let myapp = services.by_name("app", Some(Protocol::Tcp)).unwrap();
+    assert_eq!(myapp.port(), 7777);
+    assert_eq!(myapp.aliases(), &vec!["my-app", "app"]);
+    assert_eq!(myapp.description(), "My application");
+    assert_eq!(
+        services
+            .by_port(7777, Some(Protocol::Udp))
+            .unwrap()
+            .protocol(),
+        Protocol::Udp
+    );
+    assert!(Services::embedded().by_name("myapp", None).is_none());
+    let ports = Services::embedded()
+        .all()
+        .iter()
+        .map(|service| service.port())
+        .collect::<Vec<u16>>();
+    assert!(ports.is_sorted());
+
+    // a registry of its own so the shared one the other tests use is left untouched
+    let mut services = Services::embedded();
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join("services_test");
+    std::fs::write(&path, "myapp\t7777/tcp\nnotssh\t22/tcp\n").unwrap();
+    services.extend(Services::load(&path).unwrap());
+    assert_eq!(services.by_name("myapp", None).unwrap().port(), 7777);
+    assert!(services.by_name("notssh", None).is_none());
+    assert_eq!(services.len(), Services::embedded().len() + 1);
+    std::fs::remove_file(&path).unwrap();
+    let err = Services::load(&path).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidServicesFile);
+    assert!(Service::load_services_file(&path).is_err());
+    assert_eq!(port::parse_port("https").unwrap(), 443);
+    assert_eq!(port::parse_port("8080").unwrap(), 8080);
+    assert!(port::parse_port("0").is_err());
+    assert!(port::parse_port("no-such-service").is_err());
+}
+
+#[test]
+fn port_range_test() {
+    let range = PortRange::from_str("8000-8100, 22,ssh,80,https,8050-8200").unwrap();
+    assert_eq!(range.to_string(), "22,80,443,8000-8200");
+    assert_eq!(range.len(), 204);
+    assert!(range.contains(8150));
+    assert!(!range.contains(23));
+    assert_eq!(
+        range.ports().take(4).collect::<Vec<u16>>(),
+        vec![22, 80, 443, 8000]
+    );
+    assert_eq!(
+        range.ranges(),
+        &vec![(22, 22), (80, 80), (443, 443), (8000, 8200)]
+    );
+    assert_eq!(
+        PortRange::new(&[(10, 20), (21, 30)]).unwrap().to_string(),
+        "10-30"
+    );
+    assert_eq!(
+        PortRange::from_str("1-65535").unwrap().len(),
+        u16::MAX as usize
+    );
+    assert!(PortRange::from_str("100-20").is_err());
+    assert!(PortRange::from_str("0-20").is_err());
+    assert!(PortRange::from_str("22,").is_err());
+    assert!(PortRange::from_str("22-x").is_err());
+}
+
+#[test]
+fn error_test() {
+    let err = IpAddress::from_str("300.1.1.1").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidIpAddress);
+    assert_eq!(err.kind().name(), "invalid_ip_address");
+    assert_eq!(err.subsystem(), "core");
+    assert_eq!(err.input(), "300.1.1.1");
+    assert_eq!(err.to_string(), "An invalid ip address `300.1.1.1`");
+    assert!(err.source().is_none());
+    assert!(!err.location().file().ends_with("error.rs"));
+    assert_eq!(err.kind().subsystem(), "core");
+
+    let err = Network::from_str("300.1.1.1/24").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidNetwork);
+    assert_eq!(err.input(), "300.1.1.1/24");
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        "An invalid ip address `300.1.1.1`"
+    );
+    assert_eq!(err.to_string(), "An invalid ip network `300.1.1.1/24`");
+    assert_eq!(
+        format!("{err:#}"),
+        "An invalid ip network `300.1.1.1/24`: An invalid ip address `300.1.1.1`"
+    );
+
+    let err = PortRange::from_str("100-20").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidPortRange);
+
+    let err = Error::new(ErrorKind::ConnectionFailed, "10.0.0.1:22")
+        .with_source(std::io::Error::from(std::io::ErrorKind::ConnectionRefused));
+    assert_eq!(err.subsystem(), "config");
+    assert_eq!(err.to_string(), "Failed to connect `10.0.0.1:22`");
+    assert!(format!("{err:#}").starts_with("Failed to connect `10.0.0.1:22`: "));
+    assert_eq!(Error::new(ErrorKind::InvalidJson, "").subsystem(), "parser");
+    assert_eq!(
+        Error::new(ErrorKind::ScriptFailed, "").to_string(),
+        "The script failed"
+    );
+    let err = Error::from(mlua::Error::runtime("boom"));
+    assert_eq!(err.kind(), ErrorKind::ScriptFailed);
+    assert!(
+        err.source()
+            .unwrap()
+            .downcast_ref::<mlua::Error>()
+            .is_some()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde_test() {
+    #[derive(Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Host {
+        address: IpAddress,
+        gateway: IpAddress,
+        network: Network,
+        mask: Mask,
+        wildcard: WildcardMask,
+        mac: MacAddress,
+        range: IpRange,
+        ports: PortRange,
+        interface: Interface,
+    }
+    let host = Host {
+        address: IpAddress::from_str("10.0.0.12").unwrap(),
+        gateway: IpAddress::from_str("2001:db8::1").unwrap(),
+        network: Network::from_str("10.0.0.0/24").unwrap(),
+        mask: Mask::from_prefix(24).unwrap(),
+        wildcard: WildcardMask::from_str("0.0.0.255").unwrap(),
+        mac: MacAddress::from_str("00:1a:2b:3c:4d:5e").unwrap(),
+        range: IpRange::from_str("10.0.0.10-10.0.0.20").unwrap(),
+        ports: PortRange::from_str("22,80,8000-8100").unwrap(),
+        interface: Interface::by_name("lo").unwrap(),
+    };
+    let json = serde_json::to_string(&host).unwrap();
+    assert_eq!(
+        json,
+        r#"{"address":"10.0.0.12","gateway":"2001:db8::1","network":"10.0.0.0/24","mask":"255.255.255.0","wildcard":"0.0.0.255","mac":"00:1A:2B:3C:4D:5E","range":"10.0.0.10-10.0.0.20","ports":"22,80,8000-8100","interface":"lo"}"#
+    );
+    assert_eq!(serde_json::from_str::<Host>(&json).unwrap(), host);
+    let toml = toml::to_string(&host).unwrap();
+    assert!(toml.contains("network = \"10.0.0.0/24\""));
+    assert_eq!(toml::from_str::<Host>(&toml).unwrap(), host);
+
+    let set = IpSet::from_iter(vec![
+        Network::from_str("10.0.0.0/25").unwrap(),
+        Network::from_str("10.0.0.128/25").unwrap(),
+        Network::from_str("2001:db8::/32").unwrap(),
+    ]);
+    let json = serde_json::to_string(&set).unwrap();
+    assert_eq!(json, r#"["10.0.0.0/25","10.0.0.128/25","2001:db8::/32"]"#);
+    assert_eq!(
+        serde_json::from_str::<IpSet>(&json).unwrap().networks(),
+        set.networks()
+    );
+    assert!(serde_json::from_str::<IpAddress>(r#""300.1.1.1""#).is_err());
+    assert!(serde_json::from_str::<Network>("24").is_err());
+    assert!(serde_json::from_str::<Interface>(r#""nosuchif0""#).is_err());
+}
+
+#[test]
+fn lua_error_test() {
+    let lua = yrnu::lua::init().unwrap();
+    let kind = |code: &str| {
+        lua.load(format!(
+            "local value, err = {code}; return value == nil and err.kind"
+        ))
+        .eval::<String>()
+        .unwrap()
+    };
+    assert_eq!(
+        kind(r#"IpAddress("255.255.255.255"):next()"#),
+        "address_overflow"
+    );
+    assert_eq!(kind(r#"IpAddress("0.0.0.0"):prev()"#), "address_overflow");
+    assert_eq!(
+        kind(r#"IpAddress("10.0.0.1"):distance(IpAddress("::1"))"#),
+        "invalid_ip_address"
+    );
+    assert_eq!(
+        kind(r#"IpSet({"10.0.0.0/8", "nonsense"})"#),
+        "invalid_network"
+    );
+    assert_eq!(kind(r#"MacAddress.random("00:1A")"#), "invalid_mac_address");
+    assert_eq!(
+        kind(r#"Route.route_for("300.0.0.1")"#),
+        "invalid_ip_address"
+    );
+    assert_eq!(
+        kind(r#"Service.by_name("ssh", "icmp")"#),
+        "invalid_protocol"
+    );
+    assert_eq!(kind(r#"Service.by_port(22, "icmp")"#), "invalid_protocol");
+    let parsed = lua
+        .load(r#"return MacAddress.parse_all({"zz"})[1].kind"#)
+        .eval::<String>()
+        .unwrap();
+    assert_eq!(parsed, "invalid_mac_address");
+    let added = lua
+        .load(r#"return IpAddress("255.255.255.255") + 1"#)
+        .eval::<mlua::Value>()
+        .unwrap();
+    assert!(added.is_nil());
+}
+
+#[test]
+fn serialize_userdata_test() {
+    use yrnu::parser::{to_json, to_toml, to_yaml};
+    let lua = yrnu::lua::init().unwrap();
+    let value = |code: &str| lua.load(code).eval::<mlua::Value>().unwrap();
+    let table = r#"{ ip = IpAddress("10.0.0.1"), set = IpSet({"10.0.0.0/24"}) }"#;
+    let json = to_json(value(table), 10);
+    assert_eq!(json["ip"], "10.0.0.1");
+    assert_eq!(json["set"][0], "10.0.0.0/24");
+    let yaml = to_yaml(value(table), 10);
+    assert_eq!(yaml["set"][0].as_str(), Some("10.0.0.0/24"));
+    let toml = to_toml(value(table), 10).unwrap();
+    assert_eq!(toml["ip"].as_str(), Some("10.0.0.1"));
+    assert!(toml["set"].is_array());
+    let err = to_toml(value("{ file = io.stdout }"), 10).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidToml);
+}
+
+#[test]
+fn network_parse_test() {
+    let network = Network::from_str("10.0.0.0/24").unwrap();
+    for s in [
+        "10.0.0.0/24",
+        "10.0.0.0 255.255.255.0",
+        "10.0.0.0 0.0.0.255",
+        "10.0.0.0/255.255.255.0",
+        "10.0.0.0  24",
+        " 10.0.0.0/0.0.0.255 ",
+    ] {
+        assert_eq!(Network::parse(s, true).unwrap(), network, "{s}");
+        assert_eq!(Network::parse(s, false).unwrap(), network, "{s}");
+    }
+    for s in [
+        "10.0.0.5/24",
+        "10.0.0.5 255.255.255.0",
+        "10.0.0.5 0.0.0.255",
+    ] {
+        let err = Network::parse(s, true).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidNetwork);
+        assert_eq!(err.input(), s);
+        assert_eq!(Network::parse(s, false).unwrap(), network, "{s}");
+    }
+    assert_eq!(
+        Network::parse("2001:db8::1/ffff:ffff::", false).unwrap(),
+        Network::from_str("2001:db8::/32").unwrap()
+    );
+    assert_eq!(
+        Network::parse("10.0.0.5 32", true).unwrap(),
+        Network::from_str("10.0.0.5/32").unwrap()
+    );
+    assert_eq!(
+        Network::parse("10.0.0.5/0", false).unwrap(),
+        Network::from_str("0.0.0.0/0").unwrap()
+    );
+    // the all-zero and all-ones values are both a subnet and a wildcard mask
+    for s in [
+        "10.0.0.5 255.255.255.255",
+        "10.0.0.5 0.0.0.0",
+        "10.0.0.5/255.255.255.255",
+        "10.0.0.5/0.0.0.0",
+        "2001:db8::1/::",
+    ] {
+        let err = Network::parse(s, false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidNetwork);
+        assert_eq!(
+            err.source()
+                .unwrap()
+                .downcast_ref::<Error>()
+                .unwrap()
+                .kind(),
+            ErrorKind::InvalidMask,
+            "{s}"
+        );
+        assert!(!InterfaceAddress::is_valid(s), "{s}");
+    }
+    for s in [
+        "10.0.0.0",
+        "10.0.0.0/33",
+        "10.0.0.0 255.0.255.0",
+        "10.0.0.0 0.255.0.255",
+        "10.0.0.0/ffff::",
+        "10.0.0/24",
+    ] {
+        assert!(Network::parse(s, false).is_err(), "{s}");
+    }
+
+    let address = InterfaceAddress::from_str("10.0.0.5 255.255.255.0").unwrap();
+    assert_eq!(address.address(), &IpAddress::from_str("10.0.0.5").unwrap());
+    assert_eq!(address.mask(), &Mask::from_prefix(24).unwrap());
+    assert_eq!(address.network(), network);
+    assert_eq!(address.to_string(), "10.0.0.5/24");
+    assert_eq!(InterfaceAddress::from_str("10.0.0.5/24").unwrap(), address);
+    assert_eq!(
+        InterfaceAddress::from_str("10.0.0.5/0.0.0.255").unwrap(),
+        address
+    );
+    assert_eq!(
+        InterfaceAddress::from_str("fe80::1/64").unwrap().network(),
+        Network::from_str("fe80::/64").unwrap()
+    );
+    let err = InterfaceAddress::from_str("10.0.0.5").unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInterfaceAddress);
+    assert!(!InterfaceAddress::is_valid("10.0.0.5/40"));
+}
+
+/// Encodes a domain name without compression
+fn dns_name(name: &str) -> Vec<u8> {
+    let mut encoded = vec![];
+    for label in name.split('.').filter(|label| !label.is_empty()) {
+        encoded.push(label.len() as u8);
+        encoded.extend_from_slice(label.as_bytes());
+    }
+    encoded.push(0);
+    encoded
+}
+
+/// Encodes an answer record owned by the queried name, pointing at the question
+fn dns_answer(kind: u16, data: &[u8]) -> Vec<u8> {
+    let mut record = vec![0xc0, 0x0c];
+    record.extend_from_slice(&kind.to_be_bytes());
+    record.extend_from_slice(&[0, 1, 0, 0, 0x0e, 0x10]);
+    record.extend_from_slice(&(data.len() as u16).to_be_bytes());
+    record.extend_from_slice(data);
+    record
+}
+
+/// Starts a stub DNS server on the same UDP and TCP port of the loopback, the responder gets
+/// the transport, the queried name and type and returns the response flags and answers
+fn dns_stub(
+    respond: impl Fn(dns::Transport, &str, u16) -> Option<(u16, Vec<Vec<u8>>)> + Send + Sync + 'static,
+) -> u16 {
+    use std::io::{Read, Write};
+    fn response(
+        query: &[u8],
+        transport: dns::Transport,
+        respond: &dyn Fn(dns::Transport, &str, u16) -> Option<(u16, Vec<Vec<u8>>)>,
+    ) -> Option<Vec<u8>> {
+        let mut labels = vec![];
+        let mut offset = 12;
+        while query[offset] != 0 {
+            let length = query[offset] as usize;
+            labels.push(
+                String::from_utf8_lossy(&query[offset + 1..offset + 1 + length]).into_owned(),
+            );
+            offset += 1 + length;
+        }
+        let kind = u16::from_be_bytes([query[offset + 1], query[offset + 2]]);
+        let (flags, answers) = respond(transport, &labels.join("."), kind)?;
+        let mut message = query[..2].to_vec();
+        message.extend_from_slice(&flags.to_be_bytes());
+        message.extend_from_slice(&[0, 1]);
+        message.extend_from_slice(&(answers.len() as u16).to_be_bytes());
+        message.extend_from_slice(&[0, 0, 0, 0]);
+        message.extend_from_slice(&query[12..offset + 5]);
+        for answer in answers {
+            message.extend_from_slice(&answer);
+        }
+        Some(message)
+    }
+    let respond = std::sync::Arc::new(respond);
+    let udp = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let port = udp.local_addr().unwrap().port();
+    let tcp = std::net::TcpListener::bind(("127.0.0.1", port)).unwrap();
+    let udp_respond = respond.clone();
+    std::thread::spawn(move || {
+        let mut buffer = [0u8; 512];
+        while let Ok((length, peer)) = udp.recv_from(&mut buffer) {
+            if let Some(message) = response(&buffer[..length], dns::Transport::Udp, &*udp_respond) {
+                udp.send_to(&message, peer).unwrap();
+            }
+        }
+    });
+    std::thread::spawn(move || {
+        for mut stream in tcp.incoming().flatten() {
+            let mut length = [0u8; 2];
+            stream.read_exact(&mut length).unwrap();
+            let mut query = vec![0u8; u16::from_be_bytes(length) as usize];
+            stream.read_exact(&mut query).unwrap();
+            if let Some(message) = response(&query, dns::Transport::Tcp, &*respond) {
+                stream
+                    .write_all(&(message.len() as u16).to_be_bytes())
+                    .unwrap();
+                stream.write_all(&message).unwrap();
+            }
+        }
+    });
+    port
+}
+
+#[test]
+fn dns_test() {
+    use dns::{RecordData, RecordType, Resolver, Transport};
+    let port = dns_stub(|transport, name, kind| {
+        let mut mail = vec![0, 10];
+        mail.extend(dns_name("mail.example.com"));
+        let mut srv = vec![0, 1, 0, 5, 0x13, 0xc4];
+        srv.extend(dns_name("sip.example.com"));
+        let mut soa = dns_name("ns1.example.com");
+        soa.extend(dns_name("admin.example.com"));
+        for value in [2026101701u32, 7200, 3600, 1209600, 300] {
+            soa.extend(value.to_be_bytes());
+        }
+        let answers = match (name, kind) {
+            ("silent.example.com", _) => return None,
+            ("missing.example.com", _) => return Some((0x8183, vec![])),
+            ("big.example.com", _) if transport == Transport::Udp => {
+                return Some((0x8380, vec![]));
+            }
+            ("big.example.com", 1) => vec![dns_answer(1, &[10, 0, 0, 9])],
+            (_, 1) => vec![dns_answer(1, &[10, 0, 0, 1]), dns_answer(1, &[10, 0, 0, 2])],
+            (_, 28) => vec![dns_answer(
+                28,
+                &[0x20, 0x01, 0x0d, 0xb8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1],
+            )],
+            (_, 15) => vec![dns_answer(15, &mail)],
+            (_, 16) => vec![dns_answer(16, b"\x05hello\x0bv=spf1 -all")],
+            (_, 33) => vec![dns_answer(33, &srv)],
+            (_, 6) => vec![dns_answer(6, &soa)],
+            (_, 2) => vec![dns_answer(2, &dns_name("ns1.example.com"))],
+            (_, 5) => vec![dns_answer(5, &dns_name("example.com"))],
+            (_, 12) => vec![dns_answer(12, &dns_name(&format!("{name}.host")))],
+            (_, kind) => vec![dns_answer(kind, &[1, 2, 3])],
+        };
+        Some((0x8580, answers))
+    });
+    let resolver = Resolver::new(&IpAddress::from_str("127.0.0.1").unwrap())
+        .with_port(port)
+        .with_timeout(std::time::Duration::from_secs(2));
+
+    let response = resolver.query("example.com", RecordType::A).unwrap();
+    assert_eq!(response.status(), "NOERROR");
+    assert!(response.is_authoritative());
+    assert!(response.is_recursion_available());
+    assert!(!response.is_truncated());
+    assert_eq!(response.transport(), Transport::Udp);
+    assert_eq!(response.answers().len(), 2);
+    assert_eq!(response.answers()[0].name(), "example.com");
+    assert_eq!(response.answers()[0].ttl(), 3600);
+    assert_eq!(response.answers()[0].kind(), Some(RecordType::A));
+    assert_eq!(
+        response.addresses(),
+        vec![
+            IpAddress::from_str("10.0.0.1").unwrap(),
+            IpAddress::from_str("10.0.0.2").unwrap()
+        ]
+    );
+    assert_eq!(
+        response.answers()[1].to_string(),
+        "example.com 3600 IN A 10.0.0.2"
+    );
+    assert_eq!(response.raw().len(), 29 + 2 * 16);
+    assert_eq!(&response.raw()[..2], &response.id().to_be_bytes());
+
+    let data = |kind| {
+        resolver.query("example.com.", kind).unwrap().answers()[0]
+            .data()
+            .to_owned()
+    };
+    assert_eq!(
+        data(RecordType::Aaaa),
+        RecordData::Aaaa(IpAddress::from_str("2001:db8::1").unwrap())
+    );
+    assert_eq!(
+        data(RecordType::Mx),
+        RecordData::Mx {
+            preference: 10,
+            exchange: "mail.example.com".to_string()
+        }
+    );
+    assert_eq!(
+        data(RecordType::Txt),
+        RecordData::Txt(vec!["hello".to_string(), "v=spf1 -all".to_string()])
+    );
+    assert_eq!(
+        data(RecordType::Txt).to_string(),
+        r#""hello" "v=spf1 -all""#
+    );
+    assert_eq!(
+        data(RecordType::Srv).to_string(),
+        "1 5 5060 sip.example.com"
+    );
+    assert_eq!(
+        data(RecordType::Soa).to_string(),
+        "ns1.example.com admin.example.com 2026101701 7200 3600 1209600 300"
+    );
+    assert_eq!(
+        data(RecordType::Ns),
+        RecordData::Ns("ns1.example.com".to_string())
+    );
+    assert_eq!(
+        data(RecordType::Cname),
+        RecordData::Cname("example.com".to_string())
+    );
+    let response = resolver.query("10.0.0.1", RecordType::Ptr).unwrap();
+    assert_eq!(response.answers()[0].name(), "1.0.0.10.in-addr.arpa");
+    assert_eq!(
+        response.answers()[0].data(),
+        &RecordData::Ptr("1.0.0.10.in-addr.arpa.host".to_string())
+    );
+
+    let tcp = resolver.clone().with_transport(Transport::Tcp);
+    let response = tcp.query("example.com", RecordType::Mx).unwrap();
+    assert_eq!(response.transport(), Transport::Tcp);
+    assert_eq!(response.answers().len(), 1);
+
+    let response = resolver.query("big.example.com", RecordType::A).unwrap();
+    assert_eq!(response.transport(), Transport::Tcp);
+    assert_eq!(
+        response.addresses(),
+        vec![IpAddress::from_str("10.0.0.9").unwrap()]
+    );
+
+    let response = resolver
+        .query("missing.example.com", RecordType::A)
+        .unwrap();
+    assert_eq!(response.code(), 3);
+    assert_eq!(response.status(), "NXDOMAIN");
+    assert!(response.answers().is_empty());
+
+    let start = std::time::Instant::now();
+    let err = resolver
+        .clone()
+        .with_timeout(std::time::Duration::from_millis(200))
+        .query("silent.example.com", RecordType::A)
+        .unwrap_err();
+    assert!(start.elapsed() < std::time::Duration::from_secs(2));
+    assert_eq!(err.kind(), ErrorKind::DnsQueryFailed);
+    assert_eq!(err.input(), &format!("127.0.0.1:{port}"));
+    assert!(format!("{err:#}").contains("timed out"));
+
+    let err = resolver
+        .query(&format!("{}.com", "a".repeat(64)), RecordType::A)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidDomainName);
+    assert_eq!(RecordType::from_str("aaaa").unwrap(), RecordType::Aaaa);
+    assert_eq!(RecordType::from_code(33), Some(RecordType::Srv));
+    assert_eq!(
+        RecordType::from_str("AXFR").unwrap_err().kind(),
+        ErrorKind::InvalidRecordType
+    );
+    assert!(dns::Response::parse(vec![0, 1, 0x81, 0x80, 0, 1], Transport::Udp).is_err());
+    let err = dns::Response::parse(vec![0, 1, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0], Transport::Udp);
+    assert_eq!(err.unwrap_err().kind(), ErrorKind::InvalidDnsResponse);
+    // a compression pointer loop
+    let looped = vec![0, 1, 0x81, 0x80, 0, 0, 0, 1, 0, 0, 0, 0, 0xc0, 0x0c];
+    assert!(dns::Response::parse(looped, Transport::Udp).is_err());
+}
+
+#[test]
+fn dns_question_test() {
+    use dns::{RecordType, Resolver};
+    // A server that answers with the right id but echoes back another question
+    let udp = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    let port = udp.local_addr().unwrap().port();
+    std::thread::spawn(move || {
+        let mut buffer = [0u8; 512];
+        while let Ok((_, peer)) = udp.recv_from(&mut buffer) {
+            let mut message = buffer[..2].to_vec();
+            message.extend_from_slice(&[0x81, 0x80, 0, 1, 0, 0, 0, 0, 0, 0]);
+            message.extend(dns_name("spoofed.example.com"));
+            message.extend_from_slice(&[0, 1, 0, 1]);
+            udp.send_to(&message, peer).unwrap();
+        }
+    });
+    let err = Resolver::new(&IpAddress::from_str("127.0.0.1").unwrap())
+        .with_port(port)
+        .with_timeout(std::time::Duration::from_secs(2))
+        .query("example.com", RecordType::A)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidDnsResponse);
+    assert_eq!(err.input(), "question spoofed.example.com");
+
+    let resolver = Resolver::parse_resolv_conf("# comment\nnameserver fe80::1%7\n").unwrap();
+    match resolver.server() {
+        std::net::SocketAddr::V6(address) => assert_eq!(address.scope_id(), 7),
+        address => panic!("{address}"),
+    }
+    let resolver = Resolver::parse_resolv_conf("nameserver fe80::1%lo").unwrap();
+    let lo = Interface::by_name("lo").unwrap();
+    assert_eq!(
+        resolver.server().to_string(),
+        format!("[fe80::1%{}]:53", lo.index())
+    );
+    let resolver = Resolver::parse_resolv_conf("nameserver 192.0.2.53").unwrap();
+    assert_eq!(resolver.server().to_string(), "192.0.2.53:53");
+    assert!(Resolver::parse_resolv_conf("nameserver fe80::1%nosuchif0").is_none());
+}